@@ -21,12 +21,12 @@ use syn::{
     parse_quote, parse_str,
     punctuated::Punctuated,
     visit_mut::{self, VisitMut},
-    AngleBracketedGenericArguments, Attribute, DeriveInput, Expr,
-    GenericArgument, GenericParam, Generics, Ident, Item, ItemImpl, ItemStruct,
-    Lifetime, Lit, LitStr, Meta, MetaNameValue, Path, PathArguments,
-    PathSegment, PredicateLifetime, PredicateType, Token, TraitBound,
-    TraitBoundModifier, Type, TypeParam, TypeParamBound, TypePath, WhereClause,
-    WherePredicate,
+    AngleBracketedGenericArguments, Attribute, Data, DataEnum, DataStruct,
+    DeriveInput, Expr, Fields, GenericArgument, GenericParam, Generics, Ident,
+    Item, ItemImpl, ItemStruct, Lifetime, Lit, LitStr, Meta, MetaNameValue,
+    NestedMeta, Path, PathArguments, PathSegment, PredicateLifetime,
+    PredicateType, Token, TraitBound, TraitBoundModifier, Type, TypeParam,
+    TypeParamBound, TypePath, WhereClause, WherePredicate,
 };
 
 #[proc_macro_error]
@@ -38,13 +38,16 @@ pub fn derive_type_def(
         Ok(data) => data,
         Err(err) => return err.to_compile_error().into(),
     };
-    let mut input = match TypeDefInput::from_derive_input(&input) {
-        Ok(input) => input,
+    let mut input = match TypeDefInput::from_derive_input(&input).and_then(
+        |mut type_def_input| {
+            read_field_with(&mut type_def_input, &input.data)?;
+            Ok(type_def_input)
+        },
+    ) {
+        Ok(type_def_input) => type_def_input,
         Err(error) => return error.write_errors().into(),
     };
 
-    remove_skipped(&mut input.data);
-
     let generics: &mut Generics = &mut input.generics;
     if generics.params.iter().any(|param| match param {
         GenericParam::Type(_) | GenericParam::Lifetime(_) => true,
@@ -106,7 +109,8 @@ pub fn derive_type_def(
     let (impl_generics, ty_generics, where_clause) =
         input.generics.split_for_impl();
 
-    let info_def = make_info_def(&input);
+    let info_def = make_info_def(&input, Direction::Ser);
+    let info_de_def = make_info_def(&input, Direction::De);
 
     (quote! {
         impl #impl_generics ::typescript_type_def::TypeDef for
@@ -114,6 +118,8 @@ pub fn derive_type_def(
         #where_clause
         {
             const INFO: ::typescript_type_def::type_expr::TypeInfo = #info_def;
+            const INFO_DE: ::typescript_type_def::type_expr::TypeInfo =
+                #info_de_def;
         }
     })
     .into()
@@ -130,6 +136,16 @@ struct TypeDefInput {
     // type_def
     #[darling(default)]
     namespace: Namespace,
+    #[darling(default)]
+    ts_enum: SpannedValue<Flag>,
+    #[darling(default)]
+    const_object: SpannedValue<Flag>,
+    #[darling(default)]
+    brand: SpannedValue<Flag>,
+    #[darling(default)]
+    ts: Option<SpannedValue<String>>,
+    #[darling(default)]
+    ts_deps: Option<SpannedValue<TypeListFromMeta>>,
 
     // serde
     #[darling(default)]
@@ -139,33 +155,28 @@ struct TypeDefInput {
     #[darling(default)]
     untagged: SpannedValue<Flag>,
     #[darling(default)]
-    rename_all: Option<SpannedValue<String>>,
+    rename_all: SerdeRename,
     #[darling(default)]
-    rename: Option<SpannedValue<String>>,
+    rename_all_fields: SerdeRename,
     #[darling(default)]
-    #[allow(dead_code)] // doesn't affect JSON
-    transparent: Ignored,
+    rename: SerdeRename,
     #[darling(default)]
-    #[allow(dead_code)]
-    deny_unknown_fields: Ignored,
+    transparent: SpannedValue<Flag>,
+    #[darling(default)]
+    deny_unknown_fields: SpannedValue<Flag>,
     #[darling(default)]
     #[allow(dead_code)]
     bound: Ignored,
     #[darling(default)]
-    #[allow(dead_code)]
-    default: Ignored,
+    default: SpannedValue<SerdeDefault>,
     #[darling(default)]
-    #[allow(dead_code)]
-    remote: Ignored,
+    remote: Option<SpannedValue<Path>>,
     #[darling(default)]
-    #[allow(dead_code)]
-    from: Ignored,
+    from: Option<SpannedValue<TypeFromMeta>>,
     #[darling(default)]
-    #[allow(dead_code)]
-    try_from: Ignored,
+    try_from: Option<SpannedValue<TypeFromMeta>>,
     #[darling(default)]
-    #[allow(dead_code)]
-    into: Ignored,
+    into: Option<SpannedValue<TypeFromMeta>>,
     #[darling(default, rename = "crate")]
     #[allow(dead_code)]
     crate_: Ignored,
@@ -181,6 +192,12 @@ struct TypeDefField {
     // type_def
     #[darling(default)]
     type_of: Option<SpannedValue<TypeFromMeta>>,
+    #[darling(default)]
+    int_repr: Option<SpannedValue<IntRepr>>,
+    #[darling(default)]
+    ts: Option<SpannedValue<String>>,
+    #[darling(default)]
+    ts_deps: Option<SpannedValue<TypeListFromMeta>>,
 
     // serde
     #[darling(default)]
@@ -188,14 +205,81 @@ struct TypeDefField {
     #[darling(default)]
     skip_serializing_if: Option<SpannedValue<String>>,
     #[darling(default)]
-    default: SpannedValue<FieldDefault>,
+    default: SpannedValue<SerdeDefault>,
     #[darling(default)]
     skip: SpannedValue<Flag>,
     #[darling(default)]
-    rename: Option<SpannedValue<String>>,
+    rename: SerdeRename,
+    #[darling(multiple)]
+    alias: Vec<SpannedValue<String>>,
+    #[darling(default)]
+    skip_serializing: SpannedValue<Flag>,
+    #[darling(default)]
+    skip_deserializing: SpannedValue<Flag>,
+    #[darling(default)]
+    #[allow(dead_code)]
+    serialize_with: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    deserialize_with: Ignored,
+    // `#[serde(with = "...")]` names a module, so `#[type_def(with = "...")]`
+    // is read separately into `with` (see `TypeDefFieldWith`)
+    #[darling(multiple, rename = "with")]
+    #[allow(dead_code)]
+    with_attrs: Vec<Ignored>,
+    #[darling(skip)]
+    with: Option<SpannedValue<TypeFromMeta>>,
+    #[darling(default)]
+    #[allow(dead_code)]
+    borrow: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    bound: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    getter: Ignored,
+}
+
+/// The `with` option of a field, which is only read from `type_def`
+/// attributes.
+///
+/// The other options of [`TypeDefField`] are ignored here since they are
+/// already checked there.
+#[derive(FromField)]
+#[darling(attributes(type_def))]
+struct TypeDefFieldWith {
+    #[darling(default)]
+    with: Option<SpannedValue<TypeFromMeta>>,
+    #[darling(default)]
+    #[allow(dead_code)]
+    type_of: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    int_repr: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    ts: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    ts_deps: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    flatten: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    skip_serializing_if: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    default: Ignored,
+    #[darling(default)]
+    #[allow(dead_code)]
+    skip: Ignored,
     #[darling(default)]
     #[allow(dead_code)]
-    alias: Ignored,
+    rename: Ignored,
+    #[darling(multiple)]
+    #[allow(dead_code)]
+    alias: Vec<Ignored>,
     #[darling(default)]
     #[allow(dead_code)]
     skip_serializing: Ignored,
@@ -210,9 +294,6 @@ struct TypeDefField {
     deserialize_with: Ignored,
     #[darling(default)]
     #[allow(dead_code)]
-    with: Ignored,
-    #[darling(default)]
-    #[allow(dead_code)]
     borrow: Ignored,
     #[darling(default)]
     #[allow(dead_code)]
@@ -231,20 +312,17 @@ struct TypeDefVariant {
 
     // serde
     #[darling(default)]
-    rename_all: Option<SpannedValue<String>>,
+    rename_all: SerdeRename,
     #[darling(default)]
     skip: SpannedValue<Flag>,
     #[darling(default)]
-    rename: Option<SpannedValue<String>>,
+    rename: SerdeRename,
+    #[darling(multiple)]
+    alias: Vec<SpannedValue<String>>,
     #[darling(default)]
-    #[allow(dead_code)]
-    alias: Ignored,
+    skip_serializing: SpannedValue<Flag>,
     #[darling(default)]
-    #[allow(dead_code)]
-    skip_serializing: Ignored,
-    #[darling(default)]
-    #[allow(dead_code)]
-    skip_deserializing: Ignored,
+    skip_deserializing: SpannedValue<Flag>,
     #[darling(default)]
     #[allow(dead_code)]
     serialize_with: Ignored,
@@ -261,38 +339,86 @@ struct TypeDefVariant {
     #[allow(dead_code)]
     borrow: Ignored,
     #[darling(default)]
-    #[allow(dead_code)]
-    other: Ignored,
+    other: SpannedValue<Flag>,
 }
 
 #[derive(Default)]
 struct Flag(bool);
 
+/// Container options which affect how the fields of a type are described.
+#[derive(Clone, Copy)]
+struct FieldOptions {
+    direction: Direction,
+    deny_unknown_fields: bool,
+    default: bool,
+}
+
+/// Which direction of serde's data model a type definition describes.
+#[derive(Clone, Copy)]
+enum Direction {
+    /// The JSON produced when serializing a value.
+    Ser,
+    /// The JSON accepted when deserializing a value.
+    De,
+}
+
 #[derive(Default)]
 struct Namespace {
     parts: Vec<Ident>,
 }
 
 #[derive(Default)]
-struct FieldDefault(bool);
+struct SerdeDefault(bool);
 
 struct TypeFromMeta(Type);
 
-fn make_info_def(
-    TypeDefInput {
+struct TypeListFromMeta(Vec<Type>);
+
+/// A `rename`-like option, which either gives one name or separate names for
+/// serializing and deserializing (e.g. `rename(serialize = "a", deserialize =
+/// "b")`).
+#[derive(Default)]
+struct SerdeRename {
+    serialize: Option<SpannedValue<String>>,
+    deserialize: Option<SpannedValue<String>>,
+}
+
+/// The TypeScript representation of an integer field.
+#[derive(Clone, Copy)]
+enum IntRepr {
+    Number,
+    BigInt,
+    String,
+}
+
+fn make_info_def(input: &TypeDefInput, direction: Direction) -> Expr {
+    let TypeDefInput {
         attrs,
         ident: ty_name,
         generics,
         data,
         namespace,
+        ts,
+        ts_deps,
         tag,
         content,
         untagged,
         rename_all,
+        rename_all_fields,
         rename,
+        transparent,
+        deny_unknown_fields,
+        default,
+        remote,
         ..
-    }: &TypeDefInput,
-) -> Expr {
+    } = input;
+    let rename = rename.get(direction);
+    let rename_all = rename_all.get(direction);
+    let options = FieldOptions {
+        direction,
+        deny_unknown_fields: ***deny_unknown_fields,
+        default: false,
+    };
     let type_param_decls =
         generics.type_params().flat_map(|TypeParam { ident, .. }| {
             let struct_name = format_ident!("__TypeParam_{}", ident);
@@ -318,47 +444,131 @@ fn make_info_def(
             .parts
             .iter()
             .map(|part| type_ident(&part.to_string())),
-        &match rename {
-            Some(rename) => type_ident(rename.as_str()),
-            None => type_ident(&ty_name.unraw().to_string()),
-        },
-        &match data {
-            ast::Data::Struct(ast::Fields { fields, style, .. }) => {
-                if let Some(tag) = tag {
-                    abort!(tag.span(), "`tag` option is only valid for enums");
-                }
-                if let Some(content) = content {
-                    abort!(
-                        content.span(),
-                        "`content` option is only valid for enums"
-                    );
-                }
-                if ***untagged {
-                    abort!(
-                        untagged.span(),
-                        "`untagged` option is only valid for enums"
-                    );
+        &match (rename, remote) {
+            (Some(rename), _) => type_ident(rename.as_str()),
+            // a remote definition is named after the foreign type
+            (None, Some(remote)) => match remote.segments.last() {
+                Some(PathSegment { ident, .. }) => {
+                    type_ident(&ident.unraw().to_string())
                 }
+                None => abort!(remote.span(), "empty remote type path"),
+            },
+            (None, None) => type_ident(&ty_name.unraw().to_string()),
+        },
+        &match raw_type_expr(ts, ts_deps, generics, direction).or_else(|| {
+            // serialized the same as the proxy type
+            proxy_type(input, direction)
+                .map(|ty| type_expr_lazy_ref(ty, Some(generics), direction))
+        }) {
+            Some(expr) => expr,
+            None => match data {
+                ast::Data::Struct(ast::Fields { fields, style, .. }) => {
+                    if let Some(tag) = tag {
+                        if !matches!(style, ast::Style::Struct)
+                            || ***transparent
+                        {
+                            abort!(
+                                tag.span(),
+                                "`tag` option is only valid for enums and \
+                                 structs with named fields"
+                            );
+                        }
+                    }
+                    if let Some(content) = content {
+                        abort!(
+                            content.span(),
+                            "`content` option is only valid for enums"
+                        );
+                    }
+                    if ***untagged {
+                        abort!(
+                            untagged.span(),
+                            "`untagged` option is only valid for enums"
+                        );
+                    }
+                    if let Some(rename_all_fields) =
+                        rename_all_fields.get(direction)
+                    {
+                        abort!(
+                            rename_all_fields.span(),
+                            "`rename_all_fields` option is only valid for enums"
+                        );
+                    }
+                    let options = FieldOptions {
+                        default: ***default,
+                        ..options
+                    };
 
-                match style {
-                    ast::Style::Unit => type_expr_ident("null"),
-                    ast::Style::Tuple => fields_to_type_expr(
-                        fields, false, rename_all, generics, None,
-                    ),
-                    ast::Style::Struct => {
-                        if fields.is_empty() {
-                            type_expr_object([], None)
-                        } else {
-                            fields_to_type_expr(
-                                fields, true, rename_all, generics, None,
-                            )
+                    if ***transparent {
+                        // serialized the same as the one non-skipped field
+                        let field = transparent_field(fields, transparent);
+                        field_type_expr(
+                            field,
+                            field.type_of(),
+                            generics,
+                            direction,
+                        )
+                    } else {
+                        match style {
+                            ast::Style::Unit => type_expr_ident("null"),
+                            ast::Style::Tuple => fields_to_type_expr(
+                                fields, false, rename_all, generics, None,
+                                options,
+                            ),
+                            ast::Style::Struct => {
+                                let fields = if fields.is_empty() {
+                                    type_expr_object(
+                                        [],
+                                        None,
+                                        ***deny_unknown_fields,
+                                    )
+                                } else {
+                                    fields_to_type_expr(
+                                        fields, true, rename_all, generics,
+                                        None, options,
+                                    )
+                                };
+                                match tag {
+                                    Some(tag) => struct_tag_type_expr(
+                                        tag,
+                                        &match rename {
+                                            Some(rename) => rename.to_string(),
+                                            None => ty_name.unraw().to_string(),
+                                        },
+                                        fields,
+                                        options,
+                                    ),
+                                    None => fields,
+                                }
+                            }
                         }
                     }
                 }
-            }
-            ast::Data::Enum(variants) => variants_to_type_expr(
-                variants, tag, content, untagged, rename_all, generics,
-            ),
+                ast::Data::Enum(variants) => {
+                    if ***transparent {
+                        abort!(
+                            transparent.span(),
+                            "`transparent` option is only valid for structs"
+                        );
+                    }
+                    if ***default {
+                        abort!(
+                            default.span(),
+                            "`default` option is only valid for structs"
+                        );
+                    }
+                    variants_to_type_expr(
+                        variants,
+                        tag,
+                        content,
+                        untagged,
+                        rename_all,
+                        rename_all_fields.get(direction),
+                        generics,
+                        options,
+                    )
+                }
+            },
         },
         generics
             .type_params()
@@ -370,9 +580,12 @@ fn make_info_def(
                     path: ident_path(ident.clone()),
                 }),
                 None,
+                direction,
             )
         }),
         extract_type_docs(attrs).as_ref(),
+        enum_values_expr(input, direction),
+        is_branded(input, direction),
     );
     parse_quote! {{
         #(#type_param_decls)*
@@ -383,13 +596,23 @@ fn make_info_def(
 fn fields_to_type_expr(
     fields: &[TypeDefField],
     named: bool,
-    rename_all: &Option<SpannedValue<String>>,
+    rename_all: Option<&SpannedValue<String>>,
     generics: &Generics,
     docs: Option<&Expr>,
+    options: FieldOptions,
 ) -> Expr {
+    let FieldOptions {
+        direction,
+        deny_unknown_fields,
+        default: container_default,
+    } = options;
+    let fields = fields
+        .iter()
+        .filter(|field| !field.is_skipped(direction))
+        .collect::<Vec<_>>();
     if fields.is_empty() {
         return if named {
-            type_expr_object(std::iter::empty(), docs)
+            type_expr_object(std::iter::empty(), docs, deny_unknown_fields)
         } else {
             type_expr_tuple(std::iter::empty(), docs)
         };
@@ -400,89 +623,106 @@ fn fields_to_type_expr(
         }
         ***flatten
     });
-    let flatten_exprs = fields.iter().filter_map(
-        |TypeDefField {
-             ty,
-             type_of,
-             flatten,
-             ..
-         }| {
-            flatten.then(|| {
-                let ty = if let Some(type_of) = type_of {
-                    &***type_of
-                } else {
-                    ty
-                };
-                type_expr_ref(ty, Some(generics))
-            })
-        },
-    );
+    let flatten_exprs = fields
+        .iter()
+        .filter(|TypeDefField { flatten, .. }| ***flatten)
+        .map(|field| {
+            if let Some(int_repr) = &field.int_repr {
+                abort!(
+                    int_repr.span(),
+                    "`int_repr` cannot be used on flattened fields"
+                );
+            }
+            field_type_expr(field, field.type_of(), generics, direction)
+        });
     // always put flatten exprs first
     let exprs = flatten_exprs.chain((!all_flatten).then(|| {
         // if there are some non-flattened fields, make an expr out of them
-        let fields = fields.iter().filter_map(
-            |TypeDefField {
-                 attrs,
-                 ident: field_name,
-                 ty,
-                 type_of,
-                 flatten,
-                 skip_serializing_if,
-                 default,
-                 rename,
-                 ..
-             }| {
-                if ***flatten {
-                    if !named {
-                        abort!(
-                            flatten.span(),
-                            "tuple fields cannot be flattened"
-                        );
-                    }
-                    return None;
+        let fields = fields.iter().flat_map(|field| {
+            let TypeDefField {
+                attrs,
+                ident: field_name,
+                ty,
+                flatten,
+                skip_serializing_if,
+                default,
+                rename,
+                alias,
+                ..
+            } = field;
+            if ***flatten {
+                if !named {
+                    abort!(flatten.span(), "tuple fields cannot be flattened");
                 }
-                let ty = if let Some(type_of) = type_of {
-                    &***type_of
-                } else {
-                    ty
-                };
-                if let Some(field_name) = field_name {
-                    let name = type_string(
-                        &serde_rename_ident(
-                            field_name, rename, rename_all, true,
-                        )
-                        .value(),
-                        None,
-                    );
-                    let mut ty = ty;
-                    let optional = if let Some(skip_serializing_if) =
-                        skip_serializing_if
-                    {
-                        if let Some(inner_ty) = is_option(ty) {
-                            if parse_str::<Path>(skip_serializing_if).unwrap()
-                                == parse_str::<Path>("Option::is_none").unwrap()
-                            {
-                                ty = inner_ty;
+                return Vec::new();
+            }
+            let field_ty = ty;
+            let ty = field.type_of();
+            if let Some(field_name) = field_name {
+                let field_name = serde_rename_ident(
+                    field_name,
+                    rename.get(direction),
+                    rename_all,
+                    true,
+                )
+                .value();
+                let name = type_string(&field_name, None);
+                let mut ty = ty;
+                let optional = match direction {
+                    Direction::Ser => {
+                        if let Some(skip_serializing_if) = skip_serializing_if {
+                            if let Some(inner_ty) = is_option(ty) {
+                                if parse_str::<Path>(skip_serializing_if)
+                                    .unwrap()
+                                    == parse_str::<Path>("Option::is_none")
+                                        .unwrap()
+                                {
+                                    ty = inner_ty;
+                                }
                             }
+                            true
+                        } else {
+                            false
                         }
-                        true
-                    } else {
+                    }
+                    // serde fills in missing `Option` fields with `None`
+                    Direction::De => {
                         ***default
-                    };
-                    let r#type = type_expr_ref(ty, Some(generics));
-                    Some(type_object_field(
-                        &name,
-                        optional,
+                            || container_default
+                            || is_option(field_ty).is_some()
+                    }
+                };
+                // aliases are only accepted when deserializing, and any one
+                // of the names may be given
+                let aliases = match direction {
+                    Direction::Ser => &[][..],
+                    Direction::De => alias.as_slice(),
+                };
+                let optional = optional || !aliases.is_empty();
+                let r#type = field_type_expr(field, ty, generics, direction);
+                let alias_docs =
+                    type_docs(&format!("An alias of `{}`.", field_name));
+                std::iter::once(type_object_field(
+                    &name,
+                    optional,
+                    &r#type,
+                    extract_type_docs(attrs).as_ref(),
+                ))
+                .chain(aliases.iter().map(|alias| {
+                    type_object_field(
+                        &type_string(alias, None),
+                        true,
                         &r#type,
-                        extract_type_docs(attrs).as_ref(),
-                    ))
-                } else {
-                    Some(type_expr_ref(ty, Some(generics)))
-                }
-            },
-        );
+                        Some(&alias_docs),
+                    )
+                }))
+                .collect()
+            } else {
+                vec![field_type_expr(field, ty, generics, direction)]
+            }
+        });
         if named {
-            type_expr_object(fields, docs)
+            type_expr_object(fields, docs, deny_unknown_fields)
         } else {
             type_expr_tuple(fields, docs)
         }
@@ -490,169 +730,549 @@ fn fields_to_type_expr(
     type_expr_intersection(exprs, None)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn variants_to_type_expr(
     variants: &[TypeDefVariant],
     tag: &Option<SpannedValue<String>>,
     content: &Option<SpannedValue<String>>,
     untagged: &SpannedValue<Flag>,
-    variant_rename_all: &Option<SpannedValue<String>>,
+    variant_rename_all: Option<&SpannedValue<String>>,
+    rename_all_fields: Option<&SpannedValue<String>>,
     generics: &Generics,
+    options: FieldOptions,
 ) -> Expr {
     type_expr_union(
-        variants.iter().map(
-            |TypeDefVariant {
-                 attrs,
-                 ident: variant_name,
-                 fields: ast::Fields { style, fields, .. },
-                 rename_all: field_rename_all,
-                 rename: variant_rename,
-                 ..
-             }| {
+        variants
+            .iter()
+            .filter(|variant| !variant.is_skipped(options.direction))
+            .flat_map(|variant| {
+                let TypeDefVariant {
+                    attrs,
+                    ident: variant_ident,
+                    fields: ast::Fields { style, fields, .. },
+                    rename_all: field_rename_all,
+                    rename: variant_rename,
+                    alias,
+                    other,
+                    ..
+                } = variant;
                 let variant_name = serde_rename_ident(
-                    variant_name,
-                    variant_rename,
+                    variant_ident,
+                    variant_rename.get(options.direction),
                     variant_rename_all,
                     false,
-                );
-                match (tag, content, ***untagged) {
-                    (None, None, false) => match style {
-                        ast::Style::Unit => type_expr_string(
-                            &variant_name.value(),
-                            extract_type_docs(attrs).as_ref(),
-                        ),
-                        ast::Style::Tuple | ast::Style::Struct => {
-                            type_expr_object(
-                                [type_object_field(
-                                    &type_string(&variant_name.value(), None),
-                                    false,
-                                    &fields_to_type_expr(
-                                        fields,
-                                        matches!(style, ast::Style::Struct),
-                                        field_rename_all,
-                                        generics,
-                                        None,
-                                    ),
-                                    extract_type_docs(attrs).as_ref(),
-                                )],
-                                None,
-                            )
-                        }
-                    },
-                    (None, None, true) => match style {
-                        ast::Style::Unit => type_expr_ident("null"),
-                        ast::Style::Tuple | ast::Style::Struct => {
-                            fields_to_type_expr(
-                                fields,
-                                matches!(style, ast::Style::Struct),
-                                field_rename_all,
-                                generics,
-                                extract_type_docs(attrs).as_ref(),
-                            )
-                        }
-                    },
-                    (Some(tag), None, false) => match style {
-                        ast::Style::Unit => type_expr_object(
-                            [type_object_field(
-                                &type_string(tag, None),
-                                false,
-                                &type_expr_string(&variant_name.value(), None),
-                                extract_type_docs(attrs).as_ref(),
-                            )],
-                            None,
-                        ),
-                        ast::Style::Tuple | ast::Style::Struct => {
-                            if matches!(style, ast::Style::Tuple)
-                                && fields.len() != 1
-                            {
-                                abort!(
-                                    tag.span(),
-                                    "cannot tag enums with tuple variants"
-                                );
-                            }
-                            type_expr_intersection(
-                                [
+                )
+                .value();
+                // the variant's own `rename_all` takes precedence
+                let field_rename_all = field_rename_all
+                    .get(options.direction)
+                    .or(rename_all_fields);
+                // aliases are only accepted when deserializing, and untagged
+                // variants have no name
+                let aliases = match options.direction {
+                    Direction::De if !***untagged => alias.as_slice(),
+                    _ => &[],
+                };
+                let names = std::iter::once((
+                    variant_name.clone(),
+                    extract_type_docs(attrs),
+                ))
+                .chain(aliases.iter().map(|alias| {
+                    (
+                        alias.to_string(),
+                        Some(type_docs(&format!(
+                            "An alias of `{}`.",
+                            variant_name
+                        ))),
+                    )
+                }));
+                let mut members = names
+                    .map(|(name, docs)| {
+                        let variant_name = name.as_str();
+                        match (tag, content, ***untagged) {
+                            (None, None, false) => match style {
+                                ast::Style::Unit => type_expr_string(
+                                    variant_name,
+                                    docs.as_ref(),
+                                ),
+                                ast::Style::Tuple | ast::Style::Struct => {
                                     type_expr_object(
                                         [type_object_field(
-                                            &type_string(tag, None),
+                                            &type_string(variant_name, None),
                                             false,
-                                            &type_expr_string(
-                                                &variant_name.value(),
+                                            &fields_to_type_expr(
+                                                fields,
+                                                matches!(
+                                                    style,
+                                                    ast::Style::Struct
+                                                ),
+                                                field_rename_all,
+                                                generics,
                                                 None,
+                                                options,
                                             ),
-                                            extract_type_docs(attrs).as_ref(),
+                                            docs.as_ref(),
                                         )],
                                         None,
-                                    ),
+                                        false,
+                                    )
+                                }
+                            },
+                            (None, None, true) => match style {
+                                ast::Style::Unit => type_expr_ident("null"),
+                                ast::Style::Tuple | ast::Style::Struct => {
                                     fields_to_type_expr(
                                         fields,
                                         matches!(style, ast::Style::Struct),
                                         field_rename_all,
                                         generics,
-                                        None,
-                                    ),
-                                ],
-                                None,
-                            )
-                        }
-                    },
-                    (Some(tag), Some(content), false) => match style {
-                        ast::Style::Unit => type_expr_object(
-                            [type_object_field(
-                                &type_string(tag, None),
-                                false,
-                                &type_expr_string(&variant_name.value(), None),
-                                extract_type_docs(attrs).as_ref(),
-                            )],
-                            None,
-                        ),
-                        ast::Style::Tuple | ast::Style::Struct => {
-                            type_expr_object(
-                                [
-                                    type_object_field(
+                                        docs.as_ref(),
+                                        options,
+                                    )
+                                }
+                            },
+                            (Some(tag), None, false) => match style {
+                                ast::Style::Unit => type_expr_object(
+                                    [type_object_field(
                                         &type_string(tag, None),
                                         false,
-                                        &type_expr_string(
-                                            &variant_name.value(),
-                                            None,
-                                        ),
-                                        extract_type_docs(attrs).as_ref(),
-                                    ),
-                                    type_object_field(
-                                        &type_string(content, None),
+                                        &type_expr_string(variant_name, None),
+                                        docs.as_ref(),
+                                    )],
+                                    None,
+                                    false,
+                                ),
+                                ast::Style::Tuple | ast::Style::Struct => {
+                                    if matches!(style, ast::Style::Tuple)
+                                        && fields.len() != 1
+                                    {
+                                        abort!(
+                                        tag.span(),
+                                        "cannot tag enums with tuple variants"
+                                    );
+                                    }
+                                    type_expr_intersection(
+                                        [
+                                            type_expr_object(
+                                                [type_object_field(
+                                                    &type_string(tag, None),
+                                                    false,
+                                                    &type_expr_string(
+                                                        variant_name,
+                                                        None,
+                                                    ),
+                                                    docs.as_ref(),
+                                                )],
+                                                None,
+                                                false,
+                                            ),
+                                            fields_to_type_expr(
+                                                fields,
+                                                matches!(
+                                                    style,
+                                                    ast::Style::Struct
+                                                ),
+                                                field_rename_all,
+                                                generics,
+                                                None,
+                                                options,
+                                            ),
+                                        ],
+                                        None,
+                                    )
+                                }
+                            },
+                            (Some(tag), Some(content), false) => match style {
+                                ast::Style::Unit => type_expr_object(
+                                    [type_object_field(
+                                        &type_string(tag, None),
                                         false,
-                                        &fields_to_type_expr(
-                                            fields,
-                                            matches!(style, ast::Style::Struct),
-                                            field_rename_all,
-                                            generics,
-                                            None,
-                                        ),
+                                        &type_expr_string(variant_name, None),
+                                        docs.as_ref(),
+                                    )],
+                                    None,
+                                    options.deny_unknown_fields,
+                                ),
+                                ast::Style::Tuple | ast::Style::Struct => {
+                                    type_expr_object(
+                                        [
+                                            type_object_field(
+                                                &type_string(tag, None),
+                                                false,
+                                                &type_expr_string(
+                                                    variant_name,
+                                                    None,
+                                                ),
+                                                docs.as_ref(),
+                                            ),
+                                            type_object_field(
+                                                &type_string(content, None),
+                                                false,
+                                                &fields_to_type_expr(
+                                                    fields,
+                                                    matches!(
+                                                        style,
+                                                        ast::Style::Struct
+                                                    ),
+                                                    field_rename_all,
+                                                    generics,
+                                                    None,
+                                                    options,
+                                                ),
+                                                None,
+                                            ),
+                                        ],
                                         None,
-                                    ),
-                                ],
-                                None,
-                            )
+                                        options.deny_unknown_fields,
+                                    )
+                                }
+                            },
+                            (Some(tag), _, true) => {
+                                abort!(
+                                tag.span(),
+                                "cannot give both `tag` and `untagged` options"
+                            );
+                            }
+                            (None, Some(content), _) => {
+                                abort!(
+                                    content.span(),
+                                    "`content` option requires `tag` option"
+                                );
+                            }
                         }
-                    },
-                    (Some(tag), _, true) => {
+                    })
+                    .collect::<Vec<_>>();
+                if ***other {
+                    if !matches!(style, ast::Style::Unit) {
                         abort!(
-                            tag.span(),
-                            "cannot give both `tag` and `untagged` options"
+                            other.span(),
+                            "`other` option is only valid for unit variants"
                         );
                     }
-                    (None, Some(content), _) => {
-                        abort!(
-                            content.span(),
-                            "`content` option requires `tag` option"
-                        );
+                    // any unknown tag is accepted when deserializing
+                    if let Direction::De = options.direction {
+                        members.extend(other_variant_type_expr(
+                            tag, content, untagged, options,
+                        ));
                     }
                 }
-            },
-        ),
+                members
+            }),
+        None,
+    )
+}
+
+/// Returns the type expression of a struct with the `tag` option, which
+/// serializes the struct name under the tag along with the fields.
+fn struct_tag_type_expr(
+    tag: &str,
+    struct_name: &str,
+    fields: Expr,
+    options: FieldOptions,
+) -> Expr {
+    // the tag is ignored when deserializing, like any other unknown field
+    let optional = match options.direction {
+        Direction::Ser => false,
+        Direction::De if options.deny_unknown_fields => return fields,
+        Direction::De => true,
+    };
+    type_expr_intersection(
+        [
+            type_expr_object(
+                [type_object_field(
+                    &type_string(tag, None),
+                    optional,
+                    &type_expr_string(struct_name, None),
+                    None,
+                )],
+                None,
+                false,
+            ),
+            fields,
+        ],
         None,
     )
 }
 
+/// Returns the type expression of the catch-all member of an enum with a
+/// `#[serde(other)]` variant, which matches any unknown tag.
+fn other_variant_type_expr(
+    tag: &Option<SpannedValue<String>>,
+    content: &Option<SpannedValue<String>>,
+    untagged: &SpannedValue<Flag>,
+    options: FieldOptions,
+) -> Option<Expr> {
+    // `string & {}` accepts any string like `string` does, but unlike it
+    // doesn't absorb the string literals of the other variants in a union
+    let any_tag = type_expr_intersection(
+        [type_expr_ident("string"), type_expr_object([], None, false)],
+        None,
+    );
+    match (tag, ***untagged) {
+        (None, false) => Some(any_tag),
+        (Some(tag), false) => Some(type_expr_object(
+            [type_object_field(
+                &type_string(tag, None),
+                false,
+                &any_tag,
+                Some(&type_docs("Any other tag.")),
+            )],
+            None,
+            content.is_some() && options.deny_unknown_fields,
+        )),
+        (_, true) => None,
+    }
+}
+
+fn enum_values_expr(
+    TypeDefInput {
+        generics,
+        data,
+        ts_enum,
+        const_object,
+        tag,
+        untagged,
+        rename_all: variant_rename_all,
+        from,
+        try_from,
+        into,
+        ..
+    }: &TypeDefInput,
+    direction: Direction,
+) -> Option<Expr> {
+    let (style, span): (Expr, _) = match (***ts_enum, ***const_object) {
+        (false, false) => return None,
+        (true, false) => (
+            parse_quote! {
+                ::typescript_type_def::type_expr::EnumStyle::Enum
+            },
+            ts_enum.span(),
+        ),
+        (false, true) => (
+            parse_quote! {
+                ::typescript_type_def::type_expr::EnumStyle::ConstObject
+            },
+            const_object.span(),
+        ),
+        (true, true) => abort!(
+            const_object.span(),
+            "cannot give both `ts_enum` and `const_object` options"
+        ),
+    };
+    let variants = match data {
+        ast::Data::Enum(variants) => variants,
+        ast::Data::Struct(_) => {
+            abort!(
+                span,
+                "`ts_enum` and `const_object` are only valid for enums"
+            )
+        }
+    };
+    if tag.is_some() || ***untagged {
+        abort!(
+            span,
+            "`ts_enum` and `const_object` are only valid for externally tagged \
+             enums"
+        );
+    }
+    if from.is_some() || try_from.is_some() || into.is_some() {
+        abort!(
+            span,
+            "`ts_enum` and `const_object` cannot be combined with `from`, \
+             `try_from` or `into`"
+        );
+    }
+    if ***ts_enum && generics.type_params().next().is_some() {
+        abort!(span, "`ts_enum` is not valid for generic enums");
+    }
+    let members = variants
+        .iter()
+        .filter(|variant| !variant.is_skipped(direction))
+        .map(
+            |TypeDefVariant {
+                 attrs,
+                 ident: variant_name,
+                 fields: ast::Fields { style, .. },
+                 rename: variant_rename,
+                 ..
+             }| {
+                if !matches!(style, ast::Style::Unit) {
+                    abort!(
+                        variant_name.span(),
+                        "`ts_enum` and `const_object` are only valid for \
+                         enums with unit variants"
+                    );
+                }
+                let docs =
+                    wrap_optional_docs(extract_type_docs(attrs).as_ref());
+                let name = type_ident(&variant_name.unraw().to_string());
+                let value = serde_rename_ident(
+                    variant_name,
+                    variant_rename.get(direction),
+                    variant_rename_all.get(direction),
+                    false,
+                );
+                let member: Expr = parse_quote! {
+                    ::typescript_type_def::type_expr::EnumMember {
+                        docs: #docs,
+                        name: #name,
+                        value: #value,
+                    }
+                };
+                member
+            },
+        );
+    Some(parse_quote! {
+        ::typescript_type_def::type_expr::EnumValues {
+            style: #style,
+            members: &[#(#members,)*],
+        }
+    })
+}
+
+/// Returns the type given by the `into` option when serializing, or the
+/// `from` or `try_from` option when deserializing.
+fn proxy_type(
+    TypeDefInput {
+        from,
+        try_from,
+        into,
+        ..
+    }: &TypeDefInput,
+    direction: Direction,
+) -> Option<&Type> {
+    let ty = match direction {
+        Direction::Ser => into.as_ref(),
+        Direction::De => {
+            if let (Some(_), Some(try_from)) = (from, try_from) {
+                abort!(
+                    try_from.span(),
+                    "cannot give both `from` and `try_from` options"
+                );
+            }
+            from.as_ref().or(try_from.as_ref())
+        }
+    };
+    ty.map(|ty| &***ty)
+}
+
+/// Reads the `#[type_def(with = "...")]` option of each field.
+fn read_field_with(
+    input: &mut TypeDefInput,
+    data: &Data,
+) -> Result<(), darling::Error> {
+    match (&mut input.data, data) {
+        (
+            ast::Data::Struct(fields),
+            Data::Struct(DataStruct {
+                fields: syn_fields, ..
+            }),
+        ) => read_fields_with(&mut fields.fields, syn_fields),
+        (
+            ast::Data::Enum(variants),
+            Data::Enum(DataEnum {
+                variants: syn_variants,
+                ..
+            }),
+        ) => {
+            let mut errors = Vec::new();
+            for (variant, syn_variant) in variants.iter_mut().zip(syn_variants)
+            {
+                if let Err(error) = read_fields_with(
+                    &mut variant.fields.fields,
+                    &syn_variant.fields,
+                ) {
+                    errors.push(error);
+                }
+            }
+            match errors.len() {
+                0 => Ok(()),
+                _ => Err(darling::Error::multiple(errors)),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn read_fields_with(
+    fields: &mut [TypeDefField],
+    syn_fields: &Fields,
+) -> Result<(), darling::Error> {
+    let mut errors = Vec::new();
+    for (field, syn_field) in fields.iter_mut().zip(syn_fields) {
+        match TypeDefFieldWith::from_field(syn_field) {
+            Ok(TypeDefFieldWith { with, .. }) => field.with = with,
+            Err(error) => errors.push(error),
+        }
+    }
+    match errors.len() {
+        0 => Ok(()),
+        _ => Err(darling::Error::multiple(errors)),
+    }
+}
+
+/// Returns the field a `transparent` struct is serialized as.
+fn transparent_field<'a>(
+    fields: &'a [TypeDefField],
+    transparent: &SpannedValue<Flag>,
+) -> &'a TypeDefField {
+    // like serde, only fields skipped in both directions are ignored
+    let mut fields = fields.iter().filter(|field| {
+        !field.is_skipped(Direction::Ser) || !field.is_skipped(Direction::De)
+    });
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => {
+            if **field.flatten {
+                abort!(
+                    field.flatten.span(),
+                    "`transparent` structs cannot have flattened fields"
+                );
+            }
+            field
+        }
+        _ => abort!(
+            transparent.span(),
+            "`transparent` option requires exactly one non-skipped field"
+        ),
+    }
+}
+
+fn is_branded(
+    TypeDefInput {
+        data,
+        brand,
+        transparent,
+        ..
+    }: &TypeDefInput,
+    direction: Direction,
+) -> bool {
+    if !***brand {
+        return false;
+    }
+    let is_newtype = match data {
+        ast::Data::Struct(_) if ***transparent => true,
+        ast::Data::Struct(ast::Fields {
+            style: ast::Style::Tuple,
+            fields,
+            ..
+        }) => {
+            fields
+                .iter()
+                .filter(|field| !field.is_skipped(direction))
+                .count()
+                == 1
+        }
+        _ => false,
+    };
+    if !is_newtype {
+        abort!(
+            brand.span(),
+            "`brand` option is only valid for newtype structs"
+        );
+    }
+    true
+}
+
 fn type_ident(ident: &str) -> Expr {
     parse_quote! {
         ::typescript_type_def::type_expr::Ident(
@@ -681,7 +1301,107 @@ fn type_expr_ident(ident: &str) -> Expr {
     }
 }
 
-fn type_expr_ref(ty: &Type, generics: Option<&Generics>) -> Expr {
+fn type_expr_ref(
+    ty: &Type,
+    generics: Option<&Generics>,
+    direction: Direction,
+) -> Expr {
+    let ty = replace_type_params(ty, generics);
+    let info = direction.info_ident();
+    parse_quote! {
+        ::typescript_type_def::type_expr::TypeExpr::Ref(
+            &<#ty as ::typescript_type_def::TypeDef>::#info,
+        )
+    }
+}
+
+fn type_expr_lazy_ref(
+    ty: &Type,
+    generics: Option<&Generics>,
+    direction: Direction,
+) -> Expr {
+    let ty = replace_type_params(ty, generics);
+    let info = direction.info_ident();
+    parse_quote! {
+        ::typescript_type_def::type_expr::TypeExpr::LazyRef(
+            || &<#ty as ::typescript_type_def::TypeDef>::#info,
+        )
+    }
+}
+
+/// Returns the type expression of a field with the given type.
+///
+/// A `ts` option replaces the type with a raw TypeScript type. An `int_repr`
+/// replaces the field's integer type (or the inner type of an `Option` field)
+/// with the given primitive type.
+fn field_type_expr(
+    TypeDefField {
+        type_of,
+        with,
+        int_repr,
+        ts,
+        ts_deps,
+        ..
+    }: &TypeDefField,
+    ty: &Type,
+    generics: &Generics,
+    direction: Direction,
+) -> Expr {
+    if let Some(ts) = ts {
+        if type_of.is_some() || with.is_some() || int_repr.is_some() {
+            abort!(
+                ts.span(),
+                "`ts` option cannot be combined with `type_of`, `with` or \
+                 `int_repr`"
+            );
+        }
+    }
+    if let Some(raw) = raw_type_expr(ts, ts_deps, generics, direction) {
+        return raw;
+    }
+    match int_repr {
+        Some(int_repr) => {
+            if !is_integer(is_option(ty).unwrap_or(ty)) {
+                abort!(
+                    int_repr.span(),
+                    "`int_repr` option requires an integer type or an \
+                     `Option` of one"
+                );
+            }
+            let int_expr = type_expr_ident(int_repr.keyword());
+            if is_option(ty).is_some() {
+                type_expr_union([int_expr, type_expr_ident("null")], None)
+            } else {
+                int_expr
+            }
+        }
+        None => type_expr_lazy_ref(ty, Some(generics), direction),
+    }
+}
+
+/// Returns the type expression of a raw TypeScript type given using the `ts`
+/// option, if any.
+fn raw_type_expr(
+    ts: &Option<SpannedValue<String>>,
+    ts_deps: &Option<SpannedValue<TypeListFromMeta>>,
+    generics: &Generics,
+    direction: Direction,
+) -> Option<Expr> {
+    let ts = match (ts, ts_deps) {
+        (Some(ts), _) => ts,
+        (None, Some(ts_deps)) => {
+            abort!(ts_deps.span(), "`ts_deps` option requires `ts` option")
+        }
+        (None, None) => return None,
+    };
+    let deps = ts_deps
+        .iter()
+        .flat_map(|ts_deps| ts_deps.iter())
+        .map(|ty| type_expr_lazy_ref(ty, Some(generics), direction));
+    Some(type_expr_raw(ts, deps))
+}
+
+fn replace_type_params(ty: &Type, generics: Option<&Generics>) -> Type {
     let mut ty = ty.clone();
 
     if let Some(generics) = generics {
@@ -707,9 +1427,17 @@ fn type_expr_ref(ty: &Type, generics: Option<&Generics>) -> Expr {
         visit_mut::visit_type_mut(&mut TypeParamReplace { generics }, &mut ty);
     }
 
+    ty
+}
+
+fn type_expr_raw(ts: &str, deps: impl IntoIterator<Item = Expr>) -> Expr {
+    let deps = deps.into_iter();
     parse_quote! {
-        ::typescript_type_def::type_expr::TypeExpr::Ref(
-            &<#ty as ::typescript_type_def::TypeDef>::INFO,
+        ::typescript_type_def::type_expr::TypeExpr::Raw(
+            ::typescript_type_def::type_expr::TypeRaw {
+                ts: #ts,
+                deps: &[#(#deps,)*],
+            },
         )
     }
 }
@@ -766,6 +1494,7 @@ fn type_object_field(
 fn type_expr_object(
     exprs: impl IntoIterator<Item = Expr>,
     docs: Option<&Expr>,
+    deny_unknown_fields: bool,
 ) -> Expr {
     let docs = wrap_optional_docs(docs);
     let exprs = exprs.into_iter();
@@ -775,6 +1504,7 @@ fn type_expr_object(
                 docs: #docs,
                 index_signature: ::core::option::Option::None,
                 fields: &[#(#exprs,)*],
+                deny_unknown_fields: #deny_unknown_fields,
             },
         )
     }
@@ -820,6 +1550,7 @@ fn type_expr_intersection(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn type_info(
     path_parts: impl IntoIterator<Item = Expr>,
     name: &Expr,
@@ -827,8 +1558,18 @@ fn type_info(
     generic_vars: impl IntoIterator<Item = Expr>,
     generic_args: impl IntoIterator<Item = Expr>,
     docs: Option<&Expr>,
+    enum_values: Option<Expr>,
+    brand: bool,
 ) -> Expr {
     let docs = wrap_optional_docs(docs);
+    let enum_values: Expr = match enum_values {
+        Some(enum_values) => parse_quote! {
+            ::core::option::Option::Some(#enum_values)
+        },
+        None => parse_quote! {
+            ::core::option::Option::None
+        },
+    };
     let path_parts = path_parts.into_iter();
     let generic_vars = generic_vars.into_iter();
     let generic_args = generic_args.into_iter();
@@ -841,6 +1582,9 @@ fn type_info(
                     name: #name,
                     generic_vars: &[#(#generic_vars,)*],
                     def: #def,
+                    enum_values: #enum_values,
+                    brand: #brand,
+                    std_number: false,
                 },
                 generic_args: &[#(#generic_args,)*],
             },
@@ -884,12 +1628,15 @@ fn extract_type_docs(attrs: &[Attribute]) -> Option<Expr> {
             }
         }
     }
-    let docs = lines.join("\n");
-    Some(parse_quote! {
+    Some(type_docs(&lines.join("\n")))
+}
+
+fn type_docs(docs: &str) -> Expr {
+    parse_quote! {
         ::typescript_type_def::type_expr::Docs(
             #docs,
         )
-    })
+    }
 }
 
 fn wrap_optional_docs(docs: Option<&Expr>) -> Expr {
@@ -907,8 +1654,8 @@ fn wrap_optional_docs(docs: Option<&Expr>) -> Expr {
 
 fn serde_rename_ident(
     ident: &Ident,
-    rename: &Option<SpannedValue<String>>,
-    rename_all: &Option<SpannedValue<String>>,
+    rename: Option<&SpannedValue<String>>,
+    rename_all: Option<&SpannedValue<String>>,
     is_field: bool,
 ) -> LitStr {
     let span = ident.span();
@@ -937,26 +1684,82 @@ fn serde_rename_ident(
     }
 }
 
-fn remove_skipped(data: &mut ast::Data<TypeDefVariant, TypeDefField>) {
-    match data {
-        ast::Data::Struct(ast::Fields { fields, .. }) => {
-            remove_if(fields, |TypeDefField { skip, .. }| ***skip);
+impl SerdeRename {
+    fn get(&self, direction: Direction) -> Option<&SpannedValue<String>> {
+        match direction {
+            Direction::Ser => self.serialize.as_ref(),
+            Direction::De => self.deserialize.as_ref(),
         }
-        ast::Data::Enum(variants) => {
-            remove_if(
-                variants,
-                |TypeDefVariant {
-                     fields: ast::Fields { fields, .. },
-                     skip,
-                     ..
-                 }| {
-                    if ***skip {
-                        return true;
-                    }
-                    remove_if(fields, |TypeDefField { skip, .. }| ***skip);
-                    false
-                },
-            );
+    }
+}
+
+impl FromMeta for SerdeRename {
+    fn from_value(value: &Lit) -> Result<Self, darling::Error> {
+        let name = SpannedValue::new(String::from_value(value)?, value.span());
+        Ok(Self {
+            serialize: Some(name.clone()),
+            deserialize: Some(name),
+        })
+    }
+
+    fn from_list(items: &[NestedMeta]) -> Result<Self, darling::Error> {
+        #[derive(FromMeta)]
+        struct Names {
+            #[darling(default)]
+            serialize: Option<SpannedValue<String>>,
+            #[darling(default)]
+            deserialize: Option<SpannedValue<String>>,
+        }
+
+        let Names {
+            serialize,
+            deserialize,
+        } = Names::from_list(items)?;
+        Ok(Self {
+            serialize,
+            deserialize,
+        })
+    }
+}
+
+impl TypeDefField {
+    /// Returns the type whose definition is used for the field, which is
+    /// given by the `type_of` or `with` option if any.
+    fn type_of(&self) -> &Type {
+        match (&self.type_of, &self.with) {
+            (Some(_), Some(with)) => abort!(
+                with.span(),
+                "cannot give both `type_of` and `with` options"
+            ),
+            (Some(ty), None) | (None, Some(ty)) => ty,
+            (None, None) => &self.ty,
+        }
+    }
+
+    fn is_skipped(&self, direction: Direction) -> bool {
+        **self.skip
+            || match direction {
+                Direction::Ser => **self.skip_serializing,
+                Direction::De => **self.skip_deserializing,
+            }
+    }
+}
+
+impl TypeDefVariant {
+    fn is_skipped(&self, direction: Direction) -> bool {
+        **self.skip
+            || match direction {
+                Direction::Ser => **self.skip_serializing,
+                Direction::De => **self.skip_deserializing,
+            }
+    }
+}
+
+impl Direction {
+    fn info_ident(self) -> Ident {
+        match self {
+            Self::Ser => format_ident!("INFO"),
+            Self::De => format_ident!("INFO_DE"),
         }
     }
 }
@@ -990,7 +1793,7 @@ impl FromMeta for Namespace {
     }
 }
 
-impl Deref for FieldDefault {
+impl Deref for SerdeDefault {
     type Target = bool;
 
     fn deref(&self) -> &Self::Target {
@@ -998,7 +1801,7 @@ impl Deref for FieldDefault {
     }
 }
 
-impl FromMeta for FieldDefault {
+impl FromMeta for SerdeDefault {
     fn from_word() -> Result<Self, darling::Error> {
         Ok(Self(true))
     }
@@ -1008,6 +1811,27 @@ impl FromMeta for FieldDefault {
     }
 }
 
+impl IntRepr {
+    fn keyword(self) -> &'static str {
+        match self {
+            Self::Number => "number",
+            Self::BigInt => "bigint",
+            Self::String => "string",
+        }
+    }
+}
+
+impl FromMeta for IntRepr {
+    fn from_string(value: &str) -> Result<Self, darling::Error> {
+        match value {
+            "number" => Ok(Self::Number),
+            "bigint" => Ok(Self::BigInt),
+            "string" => Ok(Self::String),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
 impl Deref for TypeFromMeta {
     type Target = Type;
 
@@ -1022,6 +1846,23 @@ impl FromMeta for TypeFromMeta {
     }
 }
 
+impl Deref for TypeListFromMeta {
+    type Target = [Type];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromMeta for TypeListFromMeta {
+    fn from_string(value: &str) -> Result<Self, darling::Error> {
+        Punctuated::<Type, Token![,]>::parse_terminated
+            .parse_str(value)
+            .map(|types| Self(types.into_iter().collect()))
+            .map_err(Into::into)
+    }
+}
+
 fn is_option(ty: &Type) -> Option<&Type> {
     if let Type::Path(TypePath {
         qself: None,
@@ -1051,6 +1892,40 @@ fn is_option(ty: &Type) -> Option<&Type> {
     None
 }
 
+/// Returns whether the type is a primitive integer type or one of the
+/// `NonZero` integer types.
+fn is_integer(ty: &Type) -> bool {
+    if let Type::Path(TypePath { qself: None, path }) = ty {
+        if let Some(PathSegment {
+            ident,
+            arguments: PathArguments::None,
+        }) = path.segments.last()
+        {
+            let name = ident.to_string();
+            // `NonZeroU8` is checked as `u8`
+            let name = name
+                .strip_prefix("NonZero")
+                .map(str::to_lowercase)
+                .unwrap_or_else(|| name.clone());
+            return matches!(
+                name.as_str(),
+                "u8" | "u16"
+                    | "u32"
+                    | "u64"
+                    | "u128"
+                    | "usize"
+                    | "i8"
+                    | "i16"
+                    | "i32"
+                    | "i64"
+                    | "i128"
+                    | "isize"
+            );
+        }
+    }
+    false
+}
+
 fn ident_path(ident: Ident) -> Path {
     let mut segments = Punctuated::new();
     segments.push_value(PathSegment {
@@ -1062,17 +1937,3 @@ fn ident_path(ident: Ident) -> Path {
         segments,
     }
 }
-
-fn remove_if<T, F>(vec: &mut Vec<T>, mut filter: F)
-where
-    F: FnMut(&mut T) -> bool,
-{
-    let mut i = 0;
-    while i < vec.len() {
-        if filter(&mut vec[i]) {
-            vec.remove(i);
-        } else {
-            i += 1;
-        }
-    }
-}
@@ -1,9 +1,21 @@
-use crate::type_expr::{
-    DefinedTypeInfo, Docs, Ident, IndexSignature, NativeTypeInfo, ObjectField,
-    TypeArray, TypeDefinition, TypeExpr, TypeInfo, TypeIntersection, TypeName,
-    TypeObject, TypeString, TypeTuple, TypeUnion,
+use crate::{
+    iter_def_deps::{hash_type_definition, IterDefDeps},
+    modules::ModuleImports,
+    type_expr::{
+        DefinedTypeInfo, Docs, EnumMember, EnumStyle, EnumValues, Ident,
+        IndexSignature, NativeTypeInfo, ObjectField, TypeArray, TypeDefinition,
+        TypeExpr, TypeInfo, TypeIntersection, TypeName, TypeObject, TypeRaw,
+        TypeString, TypeTuple, TypeUnion,
+    },
+    type_guards::IntegerBounds,
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{self, Write as _},
+    io,
 };
-use std::{borrow::Cow, io};
 
 /// A Rust type that has a corresponding TypeScript type definition.
 ///
@@ -30,8 +42,8 @@ use std::{borrow::Cow, io};
 /// use serde::{Deserialize, Serialize};
 /// use typescript_type_def::{write_definition_file, TypeDef};
 ///
-/// // The Uuid type from the uuid crate does not implement TypeDef
-/// // But we know that it serializes to just a string
+/// // The Uuid type from the uuid crate does not implement TypeDef (unless the
+/// // `uuid` feature is enabled), but we know that it serializes to a string
 /// #[derive(
 ///     Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TypeDef,
 /// )]
@@ -111,6 +123,10 @@ use std::{borrow::Cow, io};
 /// | [`PathBuf`](std::path::PathBuf), [`Path`](std::path::Path) | `string` |
 /// | [`CString`](std::ffi::CString), [`CStr`](std::ffi::CStr), [`OsString`](std::ffi::OsString), [`OsStr`](std::ffi::OsStr) | `string` |
 /// | [`IpAddr`](std::net::IpAddr) | `string` |
+/// | [`Ipv4Addr`](std::net::Ipv4Addr), [`Ipv6Addr`](std::net::Ipv6Addr) | `std.Ipv4Addr`, `std.Ipv6Addr` (`string`) |
+/// | [`SocketAddr`](std::net::SocketAddr), [`SocketAddrV4`](std::net::SocketAddrV4), [`SocketAddrV6`](std::net::SocketAddrV6) | `std.SocketAddr`, `std.SocketAddrV4`, `std.SocketAddrV6` (`string`) |
+/// | [`Duration`](std::time::Duration) | `std.Duration` (`{ secs: U64; nanos: U32; }`) |
+/// | [`SystemTime`](std::time::SystemTime) | `std.SystemTime` (`{ secs_since_epoch: U64; nanos_since_epoch: U32; }`) |
 /// | numeric types | `number`[^number] |
 /// | [`()`](unit) | `null` |
 /// | [`(A, B, C)`](tuple) | `[A, B, C]` |
@@ -118,15 +134,32 @@ use std::{borrow::Cow, io};
 // FIXME: https://github.com/rust-lang/rust/issues/86375
 /// | [`Option<T>`] | <code>T \| null</code> |
 /// | [`Vec<T>`], [`[T]`](slice) | `T[]` |
+/// | [`VecDeque<T>`](std::collections::VecDeque), [`LinkedList<T>`](std::collections::LinkedList), [`BinaryHeap<T>`](std::collections::BinaryHeap) | `T[]` |
 /// | [`HashSet<T>`](std::collections::HashSet) | `T[]` |
 /// | [`BTreeSet<T>`](std::collections::BTreeSet) | `T[]` |
 /// | [`HashMap<K, V>`](std::collections::HashMap) | `Record<K, V>` |
 /// | [`BTreeMap<K, V>`](std::collections::BTreeMap) | `Record<K, V>` |
-/// | [`&'static T`](reference) | `T` |
-/// | [`Box<T>`] | `T` |
+/// | [`&'static T`](reference), [`&'static mut T`](reference) | `T` |
+/// | [`Box<T>`], [`Rc<T>`](std::rc::Rc), [`Arc<T>`](std::sync::Arc) | `T` |
 /// | [`Cow<'static, T>`](std::borrow::Cow) | `T` |
-/// | [`PhantomData<T>`](std::marker::PhantomData) | `T` |
+/// | [`Cell<T>`](std::cell::Cell), [`RefCell<T>`](std::cell::RefCell), [`Mutex<T>`](std::sync::Mutex), [`RwLock<T>`](std::sync::RwLock) | `T` |
+/// | [`Reverse<T>`](std::cmp::Reverse), [`Wrapping<T>`](std::num::Wrapping) | `T` |
+// FIXME: https://github.com/rust-lang/rust/issues/86375
+/// | [`rc::Weak<T>`](std::rc::Weak), [`sync::Weak<T>`](std::sync::Weak) | <code>T \| null</code> |
+/// | [`PhantomData<T>`](std::marker::PhantomData) | `null` |
+/// | atomic types | the same as their value type |
 /// | [`Result<T, E>`](std::result::Result) | <code>{ Ok: T } \| { Err: E }</code> |
+/// | [`Range<T>`](std::ops::Range), [`RangeInclusive<T>`](std::ops::RangeInclusive) | `std.Range<T>`, `std.RangeInclusive<T>` (`{ start: T; end: T; }`) |
+/// | [`RangeFrom<T>`](std::ops::RangeFrom) | `std.RangeFrom<T>` (`{ start: T; }`) |
+/// | [`RangeTo<T>`](std::ops::RangeTo) | `std.RangeTo<T>` (`{ end: T; }`) |
+// FIXME: https://github.com/rust-lang/rust/issues/86375
+/// | [`Bound<T>`](std::ops::Bound) | `std.Bound<T>` (<code>"Unbounded" \| { Included: T } \| { Excluded: T }</code>) |
+///
+/// Types shown as `std.Name (...)` are emitted as named definitions in a `std`
+/// namespace, documented with the format they are serialized in.
+///
+/// `std::num::Saturating<T>` has no impl since it requires a newer Rust
+/// version than this crate supports.
 ///
 /// ### [`serde_json`] Types
 ///
@@ -139,22 +172,155 @@ use std::{borrow::Cow, io};
 /// | [`Map<K, V>`](serde_json::Map) | `Record<K, V>` |
 /// | [`Number`](serde_json::Number) | `number` |
 ///
+/// ### Date and Time Types
+///
+/// [`TypeDef`] is implemented for date and time types from the
+/// [`chrono`](https://docs.rs/chrono/) and [`time`](https://docs.rs/time/)
+/// crates when the crate feature of the same name is enabled. They are emitted
+/// as named aliases with the same name as the Rust type in a namespace named
+/// after the crate (so that e.g. `chrono::Weekday` and `time::Weekday` don't
+/// conflict), documented with the format they are serialized in:
+///
+/// | Rust type | TypeScript type |
+/// |---|---|
+/// | `chrono::DateTime<Tz>` | `chrono.DateTime` (`string`) |
+/// | `chrono::NaiveDateTime` | `chrono.NaiveDateTime` (`string`) |
+/// | `chrono::NaiveDate` | `chrono.NaiveDate` (`string`) |
+/// | `chrono::NaiveTime` | `chrono.NaiveTime` (`string`) |
+/// | `chrono::TimeDelta` | `chrono.TimeDelta` (`[I64, I32]`) |
+/// | `chrono::Weekday` | `chrono.Weekday` (a union of strings) |
+/// | `chrono::Month` | `chrono.Month` (a union of strings) |
+/// | `time::OffsetDateTime` | `time.OffsetDateTime` (`string`) |
+/// | `time::PrimitiveDateTime` | `time.PrimitiveDateTime` (`string`) |
+/// | `time::UtcDateTime` | `time.UtcDateTime` (`string`) |
+/// | `time::Date` | `time.Date` (`string`) |
+/// | `time::Time` | `time.Time` (`string`) |
+/// | `time::UtcOffset` | `time.UtcOffset` (`string`) |
+/// | `time::Duration` | `time.Duration` (`string`) |
+/// | `time::Weekday` | `time.Weekday` (a union of strings) |
+/// | `time::Month` | `time.Month` (a union of strings) |
+///
+/// The `time` types are emitted in the format of the `serde-human-readable`
+/// feature of `time`, which must be enabled for them to be serialized as
+/// strings.
+///
+/// Fields using a different format (e.g. `#[serde(with =
+/// "time::serde::rfc3339")]`) should specify their type with
+/// `#[type_def(type_of = "T")]`.
+///
+/// ### Identifier Types
+///
+/// [`TypeDef`] is implemented for identifier types from the
+/// [`uuid`](https://docs.rs/uuid/), [`ulid`](https://docs.rs/ulid/) and
+/// [`bson`](https://docs.rs/bson/) crates when the crate feature of the same
+/// name is enabled:
+///
+/// | Rust type | TypeScript type |
+/// |---|---|
+/// | `uuid::Uuid` | `Uuid` (`string`) |
+/// | `ulid::Ulid` | `Ulid` (`string`) |
+/// | `bson::oid::ObjectId` | `ObjectId` (`{ "$oid": string }`) |
+///
+/// `ObjectId` also accepts a plain hexadecimal string when deserializing,
+/// which is described by its [`INFO_DE`](TypeDef::INFO_DE).
+///
+/// ### Decimal and Big Number Types
+///
+/// [`TypeDef`] is implemented for number types from the
+/// [`rust_decimal`](https://docs.rs/rust_decimal/),
+/// [`bigdecimal`](https://docs.rs/bigdecimal/),
+/// [`num-bigint`](https://docs.rs/num-bigint/) and
+/// [`ordered-float`](https://docs.rs/ordered-float/) crates when the crate
+/// feature of the same name is enabled:
+///
+/// | Rust type | TypeScript type |
+/// |---|---|
+/// | `rust_decimal::Decimal` | `Decimal` (`string`, or `number` with the `rust_decimal_float` feature) |
+/// | `bigdecimal::BigDecimal` | `BigDecimal` (`string`) |
+/// | `num_bigint::BigUint` | `BigUint` (`U32[]`) |
+/// | `num_bigint::BigInt` | `BigInt` (`[I8, BigUint]`) |
+/// | `ordered_float::OrderedFloat<T>`, `ordered_float::NotNan<T>` | `T` |
+///
+/// The `rust_decimal_float` feature assumes that the `serde-float` feature of
+/// `rust_decimal` is enabled, which this crate doesn't do itself since it
+/// changes how `Decimal` is serialized for every crate in the dependency graph.
+/// Fields using another format (e.g. `#[serde(with =
+/// "rust_decimal::serde::float")]`) should specify their type with
+/// `#[type_def(type_of = "T")]`.
+///
+/// ### Collection Types
+///
+/// [`TypeDef`] is implemented for collection types from the
+/// [`indexmap`](https://docs.rs/indexmap/),
+/// [`smallvec`](https://docs.rs/smallvec/),
+/// [`arrayvec`](https://docs.rs/arrayvec/), [`bytes`](https://docs.rs/bytes/)
+/// and [`heapless`](https://docs.rs/heapless/) crates when the crate feature
+/// of the same name is enabled:
+///
+/// | Rust type | TypeScript type |
+/// |---|---|
+/// | `indexmap::IndexMap<K, V>` | `Record<K, V>` |
+/// | `indexmap::IndexSet<T>` | `T[]` |
+/// | `smallvec::SmallVec<[T; N]>` | `T[]` |
+/// | `arrayvec::ArrayVec<T, N>` | `T[]` |
+/// | `arrayvec::ArrayString<N>` | `string` |
+/// | `bytes::Bytes`, `bytes::BytesMut` | `U8[]` |
+/// | `heapless::Vec<T, N>` | `T[]` |
+/// | `heapless::String<N>` | `string` |
+///
+/// `Bytes` and `BytesMut` can also be deserialized from a string, which is
+/// described by their [`INFO_DE`](TypeDef::INFO_DE).
+///
 /// [^number]: `std` numeric types are emitted as named aliases converted to
 /// PascalCase (e.g. `Usize`, `I32`, `F64`, `NonZeroI8`, etc.). Since they are
 /// simple aliases, they do not enforce anything in TypeScript about the Rust
-/// types' numeric bounds, but serve to document their intended range.
+/// types' numeric bounds, but serve to document their intended range. The
+/// aliases of 64-bit and 128-bit integers can instead be emitted as `bigint`
+/// or `string` (see [`IntegerRepr`]); 128-bit integers are emitted as `string`
+/// by default since most of their values cannot be represented by a
+/// JavaScript `number`.
 pub trait TypeDef: 'static {
     /// A constant value describing the structure of this type.
     ///
     /// This type information is used to emit a TypeScript type definition.
+    /// It describes the JSON which is produced when serializing this type.
     const INFO: TypeInfo;
+
+    /// A constant value describing the structure of this type when it is
+    /// deserialized.
+    ///
+    /// This can differ from [`INFO`](TypeDef::INFO) when a type accepts more
+    /// (or less) than what it produces, for example a field with
+    /// `#[serde(default)]` is always present in the serialized JSON but may be
+    /// omitted when deserializing. It defaults to [`INFO`](TypeDef::INFO).
+    ///
+    /// See [`write_definition_file_with_inputs`] for emitting these types.
+    const INFO_DE: TypeInfo = Self::INFO;
 }
 
 pub(crate) struct EmitCtx<'ctx> {
     w: &'ctx mut dyn io::Write,
     root_namespace: Option<&'ctx str>,
+    layout: Layout<'ctx>,
+    names: &'ctx DefNames,
+    int64_repr: IntegerRepr,
+    int128_repr: IntegerRepr,
+    brands: bool,
+    brand_numbers: bool,
     indent: usize,
-    stats: Stats,
+    pub(crate) stats: Stats,
+}
+
+/// How type definitions are laid out in the emitted TypeScript code.
+enum Layout<'ctx> {
+    /// Definitions are placed in namespaces (see [`ModuleStyle::Namespaces`]).
+    Namespaces,
+    /// Definitions are emitted at the top level with their namespace path as
+    /// a prefix of their name (see [`ModuleStyle::Flat`]).
+    Flat,
+    /// Definitions are emitted into one module per namespace, and references
+    /// to definitions in other modules are recorded in the imports.
+    Module(&'ctx mut ModuleImports),
 }
 
 impl EmitCtx<'_> {
@@ -190,11 +356,19 @@ pub(crate) trait Emit {
 ///
 /// The default options are:
 /// ```
-/// # use typescript_type_def::DefinitionFileOptions;
+/// # use typescript_type_def::{
+/// #     DefinitionFileOptions, IntegerRepr, ModuleStyle, NameConflicts,
+/// # };
 /// # let default =
 /// DefinitionFileOptions {
 ///     header: Some("// AUTO-GENERATED by typescript-type-def\n"),
 ///     root_namespace: Some("types"),
+///     module_style: ModuleStyle::Namespaces,
+///     name_conflicts: NameConflicts::Error,
+///     input_suffix: "Input",
+///     int64_repr: IntegerRepr::Number,
+///     int128_repr: IntegerRepr::String,
+///     brand_numbers: false,
 /// }
 /// # ;
 /// # assert_eq!(default, Default::default());
@@ -220,7 +394,274 @@ pub struct DefinitionFileOptions<'a> {
     /// `root_namespace` as well. See
     /// [this example](https://www.typescriptlang.org/play?#code/PTAEBUAsEsGdQPYFcAuBTATqFBPADmqJAIbwB2CoGCCKoZxAtmrHsQMZoA0osl0ddsTKIyAGxygARoQxoAZpjkATbJVKgAVklh0ABgDEaegHQBYAFBoAHngQY6uAqCOUAvKADk8mgFpk6BieANyWNnYO9EwsbJyg0GRkmKAA3pagGaAgEDDwCUlYToRw8WSw0MqExFHMrBzcvPwo8EIUZNBCYjXF8Hr5mCaupumZ4faO+ISuoB7efv1BoRaZWWBQJQvYk-HwKBg4CQDmalQKySiUKJCEAckISVwjGdlSqNjXoEhkAI5IxGLQeTQNCqBjMUCGYw7Uq6NDEVQJQJ4OToVQaOSKORkdhHJ6rd6EMQITpbZyQhB6GHoeGIeQEqg0FC+MRoABuaC69zQ5mWo1s41JhAAQsQsB4UqAfAgAFwuGjBUAAXyWisslmyAEF4NU5LAkGIUDwriVDtB2drBaAlPZpGghDpCHoRRgTFLKSRdts+okBkMeBR9EMeSy6FJRbKFiZnTNUpKaLK5gh-KhMJ4lUt1WAAJJ0-6culXQhFbVyT5kSpYHWM7p1Tg8NmYSRFIgaYRlphSaCHJDIeDyfUSXy-f6A4Gg6I8saRMExeqC+BpXkZKcTZzTWZS5OBEJ4lc12LFH1YRcrFZ75vrrybhY7pen7LrPJHy2tT6wIsfL4drs9nTdCHFoMUIXKcmInIWiAplgXI8qefIRKuwqijGEpSrKgGuAqyp4qqFi4ZmOQlAA7vYADW2rwOEdqosGaChqKABM6GTLAJiRtG4pxjKV5+LcQTpkAA)
     /// of a situation where not having a root namespace can lead to errors.
+    ///
+    /// This option has no effect when using [`ModuleStyle::Flat`].
     pub root_namespace: Option<&'a str>,
+    /// How the type definitions are laid out in the TypeScript module.
+    pub module_style: ModuleStyle,
+    /// How to handle distinct type definitions which have the same name and
+    /// namespace.
+    pub name_conflicts: NameConflicts,
+    /// The suffix added to the name of an input type definition which differs
+    /// from the output type definition of the same name.
+    ///
+    /// This option only has an effect when using
+    /// [`write_definition_file_with_inputs`].
+    pub input_suffix: &'a str,
+    /// How the aliases of 64-bit integer types ([`u64`], [`i64`], [`usize`],
+    /// [`isize`] and their [`NonZero`](std::num) equivalents) are emitted.
+    pub int64_repr: IntegerRepr,
+    /// How the aliases of 128-bit integer types ([`u128`], [`i128`] and their
+    /// [`NonZero`](std::num) equivalents) are emitted.
+    pub int128_repr: IntegerRepr,
+    /// Whether the aliases of `std` numeric types (e.g. `U8`, `I64`, `F64`)
+    /// are emitted as branded types.
+    ///
+    /// This makes them incompatible with each other in TypeScript, so that a
+    /// `U8` can only be created from a `number` by calling the function `U8`
+    /// which is emitted alongside it. See
+    /// [`TypeDefinition::brand`](crate::type_expr::TypeDefinition::brand).
+    pub brand_numbers: bool,
+}
+
+/// The layout of type definitions in a TypeScript module produced by
+/// [`write_definition_file`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleStyle {
+    /// Type definitions are placed in `export namespace` blocks.
+    ///
+    /// All definitions are placed under the
+    /// [`root_namespace`](DefinitionFileOptions::root_namespace) (if any), and
+    /// definitions with a namespace path from `#[type_def(namespace =
+    /// "x.y.z")]` are additionally placed in nested namespaces.
+    Namespaces,
+    /// Type definitions are emitted as top-level `export type` declarations
+    /// without any namespaces.
+    ///
+    /// The namespace path of a definition is instead used as a prefix of its
+    /// name, joined with underscores. For example, a type `Foo` in namespace
+    /// `x.y` is exported as `x_y_Foo`. This style is compatible with tools
+    /// which do not support TypeScript namespaces, such as Babel or
+    /// TypeScript's `isolatedModules` option.
+    ///
+    /// Distinct definitions with the same prefixed name (e.g. `Foo` in
+    /// namespaces `x.y_z` and `x_y.z`) are handled as name conflicts (see
+    /// [`NameConflicts`]).
+    Flat,
+}
+
+/// The TypeScript representation of an integer type which can exceed the range
+/// of integers that a JavaScript `number` can represent exactly (2^53).
+///
+/// This applies to the named aliases of large `std` integer types (e.g. `U64`,
+/// `I128`). It describes the values your JavaScript code works with, so it
+/// should match how the integers are encoded and parsed: plain JSON numbers
+/// which are parsed by `JSON.parse` lose precision, so you may want to encode
+/// them as strings (e.g. using
+/// [`serde_with::DisplayFromStr`](https://docs.rs/serde_with/latest/serde_with/struct.DisplayFromStr.html))
+/// or decode them as `bigint` using a custom JSON parser.
+///
+/// To change the representation of individual fields instead, use the
+/// `#[type_def(int_repr = "...")]` attribute of the
+/// [`TypeDef`](macro@crate::TypeDef) derive macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerRepr {
+    /// The integer is emitted as `number`.
+    Number,
+    /// The integer is emitted as `bigint`.
+    BigInt,
+    /// The integer is emitted as `string`, containing its decimal digits.
+    String,
+}
+
+/// The strategy for handling distinct type definitions which would be emitted
+/// with the same name.
+///
+/// Two definitions conflict when they have the same name and namespace but a
+/// different structure, for example two Rust types named `Config` from
+/// different modules, or a type renamed with `#[serde(rename = "...")]` to the
+/// name of another type. Definitions with the same name and the same structure
+/// are emitted only once and do not conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameConflicts {
+    /// Conflicting definitions cause an error.
+    ///
+    /// The returned [`io::Error`] has the kind
+    /// [`InvalidData`](io::ErrorKind::InvalidData) and wraps a
+    /// [`NameConflictError`] describing both definitions, which can be
+    /// retrieved using [`io::Error::get_ref`].
+    Error,
+    /// Conflicting definitions are disambiguated by appending a numeric
+    /// suffix to their names.
+    ///
+    /// The first definition keeps its name, and each following definition
+    /// with the same name gets the smallest suffix starting from `2` which
+    /// does not conflict with any other name in its namespace (e.g. `Config`,
+    /// `Config2`, `Config3`).
+    Suffix,
+}
+
+/// An error indicating that two distinct type definitions would be emitted
+/// with the same name.
+///
+/// See [`NameConflicts::Error`].
+#[derive(Debug, Clone, Copy)]
+pub struct NameConflictError {
+    /// The definition which was encountered first.
+    pub first: &'static TypeDefinition,
+    /// The definition which has the same name as `first`.
+    pub second: &'static TypeDefinition,
+}
+
+impl fmt::Display for NameConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { first, second } = self;
+        write!(f, "conflicting type definitions for `")?;
+        for Ident(path_part) in first.path {
+            write!(f, "{}.", path_part)?;
+        }
+        write!(f, "{}`: ", first.name.0)?;
+        let render = |type_def| {
+            let mut buf = Vec::new();
+            let names = DefNames::default();
+            let mut ctx =
+                EmitCtx::new(&mut buf, None, ModuleStyle::Namespaces, &names);
+            ctx.emit_type_def([type_def]).map_err(|_| fmt::Error)?;
+            String::from_utf8(buf).map_err(|_| fmt::Error)
+        };
+        write!(
+            f,
+            "`{}` and `{}`",
+            render(first)?.trim_end(),
+            render(second)?.trim_end()
+        )
+    }
+}
+
+impl Error for NameConflictError {}
+
+/// The names of type definitions, taking into account any renaming done to
+/// resolve name conflicts.
+#[derive(Default)]
+pub(crate) struct DefNames {
+    renamed: HashMap<u64, String>,
+}
+
+impl DefNames {
+    /// Resolves the names of the given definitions according to the name
+    /// conflict strategy.
+    ///
+    /// With [`ModuleStyle::Flat`], definitions conflict when their flattened
+    /// names are the same, even if their namespaces differ.
+    pub(crate) fn resolve(
+        defs: &[&'static TypeDefinition],
+        module_style: ModuleStyle,
+        name_conflicts: NameConflicts,
+    ) -> io::Result<Self> {
+        Self::resolve_with_inputs(defs, &[], "", module_style, name_conflicts)
+    }
+
+    /// Resolves the names of the given output and input definitions.
+    ///
+    /// Input definitions which have the same name as an output definition are
+    /// renamed by appending `input_suffix` before resolving any remaining
+    /// conflicts according to the name conflict strategy.
+    pub(crate) fn resolve_with_inputs(
+        defs: &[&'static TypeDefinition],
+        input_defs: &[&'static TypeDefinition],
+        input_suffix: &str,
+        module_style: ModuleStyle,
+        name_conflicts: NameConflicts,
+    ) -> io::Result<Self> {
+        // the namespace path and name which identify a definition in the
+        // output
+        let qualified_name = |path: &[Ident], name: &str| match module_style {
+            ModuleStyle::Namespaces => (qualified_path(path), name.to_owned()),
+            ModuleStyle::Flat => (Vec::new(), flat_name(path, name)),
+        };
+        let mut names = Self::default();
+        let mut used = defs
+            .iter()
+            .map(|type_def| qualified_name(type_def.path, type_def.name.0))
+            .collect::<HashSet<_>>();
+        for &type_def in input_defs {
+            let key = qualified_name(type_def.path, type_def.name.0);
+            if used.contains(&key) {
+                names.renamed.insert(
+                    hash_type_definition(type_def),
+                    format!("{}{}", type_def.name.0, input_suffix),
+                );
+            }
+        }
+        used.extend(input_defs.iter().map(|&type_def| {
+            qualified_name(type_def.path, names.get(type_def))
+        }));
+        let mut seen = HashMap::new();
+        for &type_def in defs.iter().chain(input_defs) {
+            let name = names.get(type_def).to_owned();
+            let key = qualified_name(type_def.path, &name);
+            let first = match seen.get(&key) {
+                Some(first) => *first,
+                None => {
+                    seen.insert(key, type_def);
+                    continue;
+                }
+            };
+            match name_conflicts {
+                NameConflicts::Error => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        NameConflictError {
+                            first,
+                            second: type_def,
+                        },
+                    ));
+                }
+                NameConflicts::Suffix => {
+                    let new_name = (2..)
+                        .map(|n| format!("{}{}", name, n))
+                        .find(|new_name| {
+                            !used.contains(&qualified_name(
+                                type_def.path,
+                                new_name,
+                            ))
+                        })
+                        .expect("suffixes are unbounded");
+                    used.insert(qualified_name(type_def.path, &new_name));
+                    names
+                        .renamed
+                        .insert(hash_type_definition(type_def), new_name);
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    /// Returns the name the given definition is emitted with.
+    pub(crate) fn get(&self, type_def: &'static TypeDefinition) -> &str {
+        if self.renamed.is_empty() {
+            return type_def.name.0;
+        }
+        self.renamed
+            .get(&hash_type_definition(type_def))
+            .map_or(type_def.name.0, String::as_str)
+    }
+}
+
+fn qualified_path(path: &[Ident]) -> Vec<&'static str> {
+    path.iter().map(|Ident(part)| *part).collect()
+}
+
+/// Returns the name of a definition in [`ModuleStyle::Flat`], prefixed by its
+/// namespace path.
+fn flat_name(path: &[Ident], name: &str) -> String {
+    let mut flat_name = String::new();
+    for Ident(path_part) in path {
+        flat_name.push_str(path_part);
+        flat_name.push('_');
+    }
+    flat_name.push_str(name);
+    flat_name
 }
 
 /// Statistics about the type definitions produced by [`write_definition_file`].
@@ -231,20 +672,78 @@ pub struct Stats {
 }
 
 impl<'ctx> EmitCtx<'ctx> {
-    fn new(
+    pub(crate) fn new(
         w: &'ctx mut dyn io::Write,
         root_namespace: Option<&'ctx str>,
+        module_style: ModuleStyle,
+        names: &'ctx DefNames,
     ) -> Self {
         let stats = Stats {
             type_definitions: 0,
         };
+        let layout = match module_style {
+            ModuleStyle::Namespaces => Layout::Namespaces,
+            ModuleStyle::Flat => Layout::Flat,
+        };
         Self {
             w,
             root_namespace,
+            layout,
+            names,
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+            brands: true,
+            brand_numbers: false,
             indent: 0,
             stats,
         }
     }
+
+    /// Creates a context for emitting one module of a multi-module output.
+    ///
+    /// Definitions are emitted without namespaces, and references to
+    /// definitions in other modules are recorded in `imports`.
+    pub(crate) fn new_module(
+        w: &'ctx mut dyn io::Write,
+        imports: &'ctx mut ModuleImports,
+        names: &'ctx DefNames,
+    ) -> Self {
+        Self {
+            layout: Layout::Module(imports),
+            ..Self::new(w, None, ModuleStyle::Flat, names)
+        }
+    }
+
+    /// Sets the representations of large integer type aliases.
+    pub(crate) fn with_integer_reprs(
+        self,
+        int64_repr: IntegerRepr,
+        int128_repr: IntegerRepr,
+    ) -> Self {
+        Self {
+            int64_repr,
+            int128_repr,
+            ..self
+        }
+    }
+
+    /// Sets whether the aliases of `std` numeric types are branded.
+    pub(crate) fn with_brand_numbers(self, brand_numbers: bool) -> Self {
+        Self {
+            brand_numbers,
+            ..self
+        }
+    }
+
+    /// Disables branding of type definitions, for when they describe the
+    /// output of a schema which does not produce branded values.
+    pub(crate) fn without_brands(self) -> Self {
+        Self {
+            brands: false,
+            brand_numbers: false,
+            ..self
+        }
+    }
 }
 
 struct SepList<'a, T>(&'a [T], &'static str);
@@ -288,6 +787,7 @@ impl Emit for TypeExpr {
     fn emit(&self, ctx: &mut EmitCtx<'_>) -> io::Result<()> {
         match self {
             TypeExpr::Ref(type_info) => ctx.emit_type_ref(type_info),
+            TypeExpr::LazyRef(type_info) => ctx.emit_type_ref(type_info()),
             TypeExpr::Name(type_name) => type_name.emit(ctx),
             TypeExpr::String(type_string) => type_string.emit(ctx),
             TypeExpr::Tuple(type_tuple) => type_tuple.emit(ctx),
@@ -297,6 +797,7 @@ impl Emit for TypeExpr {
             TypeExpr::Intersection(type_intersection) => {
                 type_intersection.emit(ctx)
             }
+            TypeExpr::Raw(type_raw) => type_raw.emit(ctx),
         }
     }
 }
@@ -322,7 +823,7 @@ impl Emit for TypeString {
     fn emit(&self, ctx: &mut EmitCtx<'_>) -> io::Result<()> {
         let Self { docs, value } = self;
         docs.emit(ctx)?;
-        write!(ctx.w, "{:?}", value)?;
+        write!(ctx.w, "{}", JsString(value))?;
         Ok(())
     }
 }
@@ -344,6 +845,7 @@ impl Emit for TypeObject {
             docs,
             index_signature,
             fields,
+            deny_unknown_fields: _,
         } = self;
         if let Some(docs) = docs {
             docs.emit(ctx)?;
@@ -424,6 +926,14 @@ impl Emit for TypeIntersection {
     }
 }
 
+impl Emit for TypeRaw {
+    fn emit(&self, ctx: &mut EmitCtx<'_>) -> io::Result<()> {
+        let Self { ts, deps: _ } = self;
+        write!(ctx.w, "({})", ts)?;
+        Ok(())
+    }
+}
+
 impl Emit for Ident {
     fn emit(&self, ctx: &mut EmitCtx<'_>) -> io::Result<()> {
         let Self(name) = self;
@@ -468,17 +978,29 @@ where
 }
 
 impl EmitCtx<'_> {
-    fn emit_type_def(&mut self, infos: &[&'static TypeInfo]) -> io::Result<()> {
-        for TypeDefinition {
-            docs,
-            path,
-            name,
-            generic_vars,
-            def,
-        } in crate::iter_def_deps::IterDefDeps::new(infos)
-        {
+    pub(crate) fn emit_type_def<I>(&mut self, defs: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'static TypeDefinition>,
+    {
+        for type_def in defs {
+            let TypeDefinition {
+                docs,
+                path,
+                name: _,
+                generic_vars,
+                def: _,
+                enum_values,
+                brand: _,
+                std_number: _,
+            } = type_def;
+            let names = self.names;
+            let name = names.get(type_def);
             self.stats.type_definitions += 1;
-            if !path.is_empty() {
+            let namespaced = match self.layout {
+                Layout::Namespaces => !path.is_empty(),
+                Layout::Flat | Layout::Module(_) => false,
+            };
+            if namespaced {
                 write!(
                     self.w,
                     "{}export namespace ",
@@ -489,13 +1011,95 @@ impl EmitCtx<'_> {
                 self.indent();
             }
             docs.emit(self)?;
-            write!(self.w, "{}export type ", self.current_indentation())?;
-            name.emit(self)?;
-            Generics(generic_vars).emit(self)?;
-            write!(self.w, " = ")?;
-            def.emit(self)?;
-            write!(self.w, ";")?;
-            if !path.is_empty() {
+            match enum_values {
+                Some(EnumValues {
+                    style: EnumStyle::Enum,
+                    members,
+                }) => {
+                    write!(
+                        self.w,
+                        "{}export enum ",
+                        self.current_indentation()
+                    )?;
+                    self.emit_decl_name(path, name)?;
+                    write!(self.w, " ")?;
+                    self.emit_enum_members(members, " = ")?;
+                }
+                Some(EnumValues {
+                    style: EnumStyle::ConstObject,
+                    members: _,
+                })
+                | None => {
+                    write!(
+                        self.w,
+                        "{}export type ",
+                        self.current_indentation()
+                    )?;
+                    self.emit_decl_name(path, name)?;
+                    Generics(generic_vars).emit(self)?;
+                    write!(self.w, " = ")?;
+                    if let Some(brand) = self.brand_name(type_def) {
+                        write!(self.w, "(")?;
+                        self.emit_def_body(type_def)?;
+                        write!(
+                            self.w,
+                            " & {{ readonly __brand: {} }})",
+                            JsString(&brand)
+                        )?;
+                        write!(self.w, ";")?;
+                        writeln!(self.w)?;
+                        write!(
+                            self.w,
+                            "{}export const ",
+                            self.current_indentation()
+                        )?;
+                        self.emit_decl_name(path, name)?;
+                        write!(self.w, " = ")?;
+                        Generics(generic_vars).emit(self)?;
+                        write!(self.w, "(value: ")?;
+                        self.emit_def_body(type_def)?;
+                        write!(self.w, "): ")?;
+                        self.emit_decl_name(path, name)?;
+                        Generics(generic_vars).emit(self)?;
+                        write!(self.w, " => value as ")?;
+                        self.emit_decl_name(path, name)?;
+                        Generics(generic_vars).emit(self)?;
+                    } else {
+                        self.emit_def_body(type_def)?;
+                    }
+                    write!(self.w, ";")?;
+                }
+            }
+            if let Some(EnumValues { style, members }) = enum_values {
+                if let EnumStyle::ConstObject = style {
+                    writeln!(self.w)?;
+                    write!(
+                        self.w,
+                        "{}export const ",
+                        self.current_indentation()
+                    )?;
+                    self.emit_decl_name(path, name)?;
+                    write!(self.w, " = ")?;
+                    self.emit_enum_members(members, ": ")?;
+                    write!(self.w, " as const;")?;
+                }
+                writeln!(self.w)?;
+                write!(self.w, "{}export const ", self.current_indentation())?;
+                self.emit_decl_name(path, name)?;
+                write!(self.w, "Values = [")?;
+                for (i, EnumMember { name: member, .. }) in
+                    members.iter().enumerate()
+                {
+                    if i > 0 {
+                        write!(self.w, ", ")?;
+                    }
+                    self.emit_decl_name(path, name)?;
+                    write!(self.w, ".")?;
+                    member.emit(self)?;
+                }
+                write!(self.w, "] as const;")?;
+            }
+            if namespaced {
                 writeln!(self.w)?;
                 self.deindent();
                 write!(self.w, "{}}}", self.current_indentation())?;
@@ -505,33 +1109,120 @@ impl EmitCtx<'_> {
         Ok(())
     }
 
+    /// Emits the body of a type definition, without any brand.
+    fn emit_def_body(
+        &mut self,
+        type_def: &'static TypeDefinition,
+    ) -> io::Result<()> {
+        match IntegerRepr::of(type_def, self.int64_repr, self.int128_repr) {
+            Some(repr) => write!(self.w, "{}", repr.keyword()),
+            None => type_def.def.emit(self),
+        }
+    }
+
+    /// Returns the brand of a type definition if it is emitted as a branded
+    /// type.
+    pub(crate) fn brand_name(
+        &self,
+        type_def: &'static TypeDefinition,
+    ) -> Option<String> {
+        let is_branded = (self.brands && type_def.brand)
+            || (self.brand_numbers && type_def.std_number);
+        is_branded.then(|| brand_name(self.names, type_def))
+    }
+
+    /// Emits the name of a type definition being declared.
+    fn emit_decl_name(&mut self, path: &[Ident], name: &str) -> io::Result<()> {
+        match self.layout {
+            Layout::Flat => self.emit_flat_name(path, name),
+            Layout::Namespaces | Layout::Module(_) => {
+                write!(self.w, "{}", name)
+            }
+        }
+    }
+
+    /// Emits the body of an enum or object with the given members, separating
+    /// each member name and value with `sep`.
+    fn emit_enum_members(
+        &mut self,
+        members: &[EnumMember],
+        sep: &str,
+    ) -> io::Result<()> {
+        writeln!(self.w, "{{")?;
+        self.indent();
+        for EnumMember { docs, name, value } in members {
+            docs.emit(self)?;
+            write!(self.w, "{}", self.current_indentation())?;
+            name.emit(self)?;
+            writeln!(self.w, "{}{},", sep, JsString(value))?;
+        }
+        self.deindent();
+        write!(self.w, "{}}}", self.current_indentation())
+    }
+
     fn emit_type_ref(&mut self, info: &'static TypeInfo) -> io::Result<()> {
         match info {
             TypeInfo::Native(NativeTypeInfo { r#ref }) => r#ref.emit(self),
             TypeInfo::Defined(DefinedTypeInfo {
-                def:
-                    TypeDefinition {
-                        docs: _,
-                        path,
-                        name,
-                        generic_vars: _,
-                        def: _,
-                    },
+                def: type_def,
                 generic_args,
             }) => {
-                if let Some(root_namespace) = self.root_namespace {
-                    write!(self.w, "{}.", root_namespace)?;
-                }
+                self.emit_def_name(type_def)?;
+                Generics(generic_args).emit(self)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Emits the qualified name of a type definition, without any generic
+    /// arguments.
+    ///
+    /// When emitting a module, this is the name the definition is available
+    /// as in that module, importing it if needed.
+    pub(crate) fn emit_def_name(
+        &mut self,
+        type_def: &'static TypeDefinition,
+    ) -> io::Result<()> {
+        let TypeDefinition {
+            docs: _,
+            path,
+            name: _,
+            generic_vars: _,
+            def: _,
+            enum_values,
+            brand: _,
+            std_number: _,
+        } = type_def;
+        let names = self.names;
+        let name = names.get(type_def);
+        let has_value =
+            enum_values.is_some() || self.brand_name(type_def).is_some();
+        if let Some(root_namespace) = self.root_namespace {
+            write!(self.w, "{}.", root_namespace)?;
+        }
+        match &mut self.layout {
+            Layout::Namespaces => {
                 for path_part in *path {
                     path_part.emit(self)?;
                     write!(self.w, ".")?;
                 }
-                name.emit(self)?;
-                Generics(generic_args).emit(self)?;
-                Ok(())
+                write!(self.w, "{}", name)
+            }
+            Layout::Flat => self.emit_flat_name(path, name),
+            Layout::Module(imports) => {
+                let local_name = imports.local_name(path, name, has_value);
+                write!(self.w, "{}", local_name)
             }
         }
     }
+
+    fn emit_flat_name(&mut self, path: &[Ident], name: &str) -> io::Result<()> {
+        for path_part in path {
+            path_part.emit(self)?;
+            write!(self.w, "_")?;
+        }
+        write!(self.w, "{}", name)
+    }
 }
 
 impl Default for DefinitionFileOptions<'_> {
@@ -539,6 +1230,77 @@ impl Default for DefinitionFileOptions<'_> {
         Self {
             header: Some("// AUTO-GENERATED by typescript-type-def\n"),
             root_namespace: Some("types"),
+            module_style: ModuleStyle::Namespaces,
+            name_conflicts: NameConflicts::Error,
+            input_suffix: "Input",
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+            brand_numbers: false,
+        }
+    }
+}
+
+/// A string which is displayed as a JavaScript string literal.
+pub(crate) struct JsString<'a>(pub(crate) &'a str);
+
+impl fmt::Display for JsString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(value) = self;
+        f.write_char('"')?;
+        for c in value.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                // line separators are not allowed in string literals before
+                // ES2019
+                c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                    write!(f, "\\u{:04x}", c as u32)?
+                }
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+/// Returns the unique brand of a type definition, which is its name qualified
+/// by its namespace path.
+pub(crate) fn brand_name(
+    names: &DefNames,
+    type_def: &'static TypeDefinition,
+) -> String {
+    let mut brand = String::new();
+    for Ident(path_part) in type_def.path {
+        brand.push_str(path_part);
+        brand.push('.');
+    }
+    brand.push_str(names.get(type_def));
+    brand
+}
+
+impl IntegerRepr {
+    /// Returns the representation of a type definition if it is the alias of a
+    /// large `std` integer type.
+    pub(crate) fn of(
+        type_def: &TypeDefinition,
+        int64_repr: Self,
+        int128_repr: Self,
+    ) -> Option<Self> {
+        match IntegerBounds::of(type_def)?.bits {
+            64 => Some(int64_repr),
+            128 => Some(int128_repr),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn keyword(self) -> &'static str {
+        match self {
+            Self::Number => "number",
+            Self::BigInt => "bigint",
+            Self::String => "string",
         }
     }
 }
@@ -558,6 +1320,10 @@ impl Default for DefinitionFileOptions<'_> {
 /// [`root_namespace`](DefinitionFileOptions::root_namespace) for an important
 /// note about using no root namespace.
 ///
+/// If the [`module_style`](DefinitionFileOptions::module_style) is set to
+/// [`ModuleStyle::Flat`], no namespaces and no default export will be added,
+/// and every type definition is exported at the top level of the module.
+///
 /// The file will also include a header comment indicating that it was
 /// auto-generated by this library. This is configurable with the
 /// [`header`](DefinitionFileOptions::header) option.
@@ -565,13 +1331,13 @@ impl Default for DefinitionFileOptions<'_> {
 /// Note that the TypeScript code generated by this library is not very
 /// human-readable. To make the code human-readable, use a TypeScript code
 /// formatter (such as [Prettier](https://prettier.io/)) on the output.
-pub fn write_definition_file<W, T: ?Sized>(
+pub fn write_definition_file<W, T>(
     writer: W,
     options: DefinitionFileOptions<'_>,
 ) -> io::Result<Stats>
 where
     W: io::Write,
-    T: TypeDef,
+    T: TypeDef + ?Sized,
 {
     write_definition_file_from_type_infos(writer, options, &[&T::INFO])
 }
@@ -593,6 +1359,10 @@ where
 /// [`root_namespace`](DefinitionFileOptions::root_namespace) for an important
 /// note about using no root namespace.
 ///
+/// If the [`module_style`](DefinitionFileOptions::module_style) is set to
+/// [`ModuleStyle::Flat`], no namespaces and no default export will be added,
+/// and every type definition is exported at the top level of the module.
+///
 /// The file will also include a header comment indicating that it was
 /// auto-generated by this library. This is configurable with the
 /// [`header`](DefinitionFileOptions::header) option.
@@ -601,24 +1371,123 @@ where
 /// human-readable. To make the code human-readable, use a TypeScript code
 /// formatter (such as [Prettier](https://prettier.io/)) on the output.
 pub fn write_definition_file_from_type_infos<W>(
+    writer: W,
+    options: DefinitionFileOptions<'_>,
+    type_infos: &[&'static TypeInfo],
+) -> io::Result<Stats>
+where
+    W: io::Write,
+{
+    write_definition_file_from_type_infos_with_inputs(
+        writer,
+        options,
+        type_infos,
+        &[],
+    )
+}
+
+/// Writes a TypeScript definition file containing type definitions for both
+/// the output and input shape of `T` to the given writer.
+///
+/// The output shape of a type is described by [`TypeDef::INFO`] and is the
+/// JSON produced when serializing it, while the input shape is described by
+/// [`TypeDef::INFO_DE`] and is the JSON accepted when deserializing it. When
+/// these differ, for example because of `#[serde(default)]` or
+/// `#[serde(skip_deserializing)]`, the input type definition is emitted with
+/// the [`input_suffix`](DefinitionFileOptions::input_suffix) appended to its
+/// name. Type definitions which are the same in both directions are emitted
+/// only once.
+///
+/// Otherwise, this function behaves the same as [`write_definition_file`].
+///
+/// # Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use typescript_type_def::{
+///     write_definition_file_with_inputs,
+///     DefinitionFileOptions,
+///     TypeDef,
+/// };
+///
+/// #[derive(Serialize, Deserialize, TypeDef)]
+/// struct Request {
+///     #[serde(default)]
+///     limit: u32,
+/// }
+///
+/// let mut buf = Vec::new();
+/// let options = DefinitionFileOptions {
+///     header: None,
+///     ..Default::default()
+/// };
+/// write_definition_file_with_inputs::<_, Request>(&mut buf, options).unwrap();
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     r#"export default types;
+/// export namespace types {
+///     export type U32 = number;
+///     export type Request = {
+///         "limit": types.U32;
+///     };
+///     export type RequestInput = {
+///         "limit"?: types.U32;
+///     };
+/// }
+/// "#
+/// );
+/// ```
+pub fn write_definition_file_with_inputs<W, T>(
+    writer: W,
+    options: DefinitionFileOptions<'_>,
+) -> io::Result<Stats>
+where
+    W: io::Write,
+    T: TypeDef + ?Sized,
+{
+    write_definition_file_from_type_infos_with_inputs(
+        writer,
+        options,
+        &[&T::INFO],
+        &[&T::INFO_DE],
+    )
+}
+
+/// Writes a TypeScript definition file containing type definitions for the
+/// given lists of output and input type info values to the given writer.
+///
+/// The output type info values can be obtained using [`TypeDef::INFO`] and the
+/// input type info values using [`TypeDef::INFO_DE`] on a type. See
+/// [`write_definition_file_with_inputs`] for how input type definitions are
+/// named.
+pub fn write_definition_file_from_type_infos_with_inputs<W>(
     mut writer: W,
     options: DefinitionFileOptions<'_>,
     type_infos: &[&'static TypeInfo],
+    input_type_infos: &[&'static TypeInfo],
 ) -> io::Result<Stats>
 where
     W: io::Write,
 {
-    let mut ctx = EmitCtx::new(&mut writer, options.root_namespace);
+    let root_namespace = match options.module_style {
+        ModuleStyle::Namespaces => options.root_namespace,
+        ModuleStyle::Flat => None,
+    };
+    let (defs, input_defs, names) =
+        definition_file_defs(&options, type_infos, input_type_infos)?;
+    let mut ctx =
+        EmitCtx::new(&mut writer, root_namespace, options.module_style, &names)
+            .with_integer_reprs(options.int64_repr, options.int128_repr)
+            .with_brand_numbers(options.brand_numbers);
     if let Some(header) = options.header {
         writeln!(&mut ctx.w, "{}", header)?;
     }
-    if let Some(root_namespace) = options.root_namespace {
+    if let Some(root_namespace) = root_namespace {
         writeln!(&mut ctx.w, "export default {};", root_namespace)?;
         writeln!(&mut ctx.w, "export namespace {} {{", root_namespace)?;
         ctx.indent();
     }
-    ctx.emit_type_def(type_infos)?;
-    if options.root_namespace.is_some() {
+    ctx.emit_type_def(defs.into_iter().chain(input_defs))?;
+    if root_namespace.is_some() {
         ctx.deindent();
         writeln!(&mut ctx.w, "}}")?;
     }
@@ -626,6 +1495,38 @@ where
     Ok(ctx.stats)
 }
 
+/// Returns the output and input type definitions of a definition file along
+/// with the names they are emitted with.
+#[allow(clippy::type_complexity)]
+fn definition_file_defs(
+    options: &DefinitionFileOptions<'_>,
+    type_infos: &[&'static TypeInfo],
+    input_type_infos: &[&'static TypeInfo],
+) -> io::Result<(
+    Vec<&'static TypeDefinition>,
+    Vec<&'static TypeDefinition>,
+    DefNames,
+)> {
+    let defs = IterDefDeps::new(type_infos).collect::<Vec<_>>();
+    let output_hashes = defs
+        .iter()
+        .map(|&type_def| hash_type_definition(type_def))
+        .collect::<HashSet<_>>();
+    let input_defs = IterDefDeps::new(input_type_infos)
+        .filter(|&type_def| {
+            !output_hashes.contains(&hash_type_definition(type_def))
+        })
+        .collect::<Vec<_>>();
+    let names = DefNames::resolve_with_inputs(
+        &defs,
+        &input_defs,
+        options.input_suffix,
+        options.module_style,
+        options.name_conflicts,
+    )?;
+    Ok((defs, input_defs, names))
+}
+
 impl TypeInfo {
     /// Writes a Typescript type expression referencing this type to the given
     /// writer.
@@ -635,6 +1536,11 @@ impl TypeInfo {
     /// `root_namespace` option should be set to the qualified name of the
     /// import of that module.
     ///
+    /// The reference assumes the default
+    /// [`module_style`](DefinitionFileOptions::module_style) and that the type
+    /// was not renamed in that module. Otherwise, use
+    /// [`write_ref_expr_with_options`](Self::write_ref_expr_with_options).
+    ///
     /// # Example
     /// ```
     /// use serde::Serialize;
@@ -673,7 +1579,116 @@ impl TypeInfo {
     where
         W: io::Write,
     {
-        let mut ctx = EmitCtx::new(&mut writer, root_namespace);
+        let names = DefNames::default();
+        let mut ctx = EmitCtx::new(
+            &mut writer,
+            root_namespace,
+            ModuleStyle::Namespaces,
+            &names,
+        );
+        ctx.emit_type_ref(self)?;
+        debug_assert_eq!(ctx.indent, 0, "indentation must be 0 after printing");
+        Ok(())
+    }
+
+    /// Writes a Typescript type expression referencing this type to the given
+    /// writer, matching a module created with the given options.
+    ///
+    /// This method is the same as [`write_ref_expr`](Self::write_ref_expr),
+    /// except that the reference matches a module created with
+    /// [`write_definition_file_from_type_infos_with_inputs`] using the same
+    /// `options`, `type_infos` and `input_type_infos` (which is empty for a
+    /// module created without input definitions). This takes into account the
+    /// [`module_style`](DefinitionFileOptions::module_style) as well as the
+    /// names given to definitions by
+    /// [`input_suffix`](DefinitionFileOptions::input_suffix) and
+    /// [`NameConflicts::Suffix`].
+    ///
+    /// # Errors
+    ///
+    /// The same name conflicts as for the module cause an error.
+    ///
+    /// # Example
+    /// ```
+    /// use serde::Serialize;
+    /// use std::io::Write;
+    /// use typescript_type_def::{
+    ///     DefinitionFileOptions,
+    ///     ModuleStyle,
+    ///     NameConflicts,
+    ///     TypeDef,
+    /// };
+    ///
+    /// mod a {
+    ///     #[derive(serde::Serialize, typescript_type_def::TypeDef)]
+    ///     pub struct Config {
+    ///         pub a: String,
+    ///     }
+    /// }
+    ///
+    /// mod b {
+    ///     #[derive(serde::Serialize, typescript_type_def::TypeDef)]
+    ///     pub struct Config {
+    ///         pub b: String,
+    ///     }
+    /// }
+    ///
+    /// #[derive(Serialize, TypeDef)]
+    /// struct Api {
+    ///     a: a::Config,
+    ///     b: b::Config,
+    /// }
+    ///
+    /// // types.ts contains type definitions written using write_definition_file
+    /// let options = DefinitionFileOptions {
+    ///     module_style: ModuleStyle::Flat,
+    ///     name_conflicts: NameConflicts::Suffix,
+    ///     ..Default::default()
+    /// };
+    /// let ts_module = {
+    ///     let mut buf = Vec::new();
+    ///     writeln!(&mut buf, "import * as types from './types';").unwrap();
+    ///     writeln!(&mut buf).unwrap();
+    ///     write!(&mut buf, "export function myAPI(config: ").unwrap();
+    ///     b::Config::INFO
+    ///         .write_ref_expr_with_options(
+    ///             &mut buf,
+    ///             Some("types"),
+    ///             options,
+    ///             &[&Api::INFO],
+    ///             &[],
+    ///         )
+    ///         .unwrap();
+    ///     writeln!(&mut buf, ") {{}}").unwrap();
+    ///     String::from_utf8(buf).unwrap()
+    /// };
+    /// assert_eq!(
+    ///     ts_module,
+    ///     r#"import * as types from './types';
+    ///
+    /// export function myAPI(config: types.Config2) {}
+    /// "#
+    /// );
+    /// ```
+    pub fn write_ref_expr_with_options<W>(
+        &'static self,
+        mut writer: W,
+        root_namespace: Option<&str>,
+        options: DefinitionFileOptions<'_>,
+        type_infos: &[&'static TypeInfo],
+        input_type_infos: &[&'static TypeInfo],
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let (_, _, names) =
+            definition_file_defs(&options, type_infos, input_type_infos)?;
+        let mut ctx = EmitCtx::new(
+            &mut writer,
+            root_namespace,
+            options.module_style,
+            &names,
+        );
         ctx.emit_type_ref(self)?;
         debug_assert_eq!(ctx.indent, 0, "indentation must be 0 after printing");
         Ok(())
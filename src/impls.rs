@@ -19,6 +19,7 @@ macro_rules! impl_native {
 
 /// A Rust equivalent to the JavaScript
 /// [`Blob`](https://developer.mozilla.org/en-US/docs/Web/API/Blob) object.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Blob(pub Vec<u8>);
 
@@ -47,6 +48,9 @@ macro_rules! impl_number {
                     name: Ident(stringify!($name)),
                     generic_vars: &[],
                     def: TypeExpr::ident(Ident("number")),
+                    enum_values: None,
+                    brand: false,
+                    std_number: true,
                 },
                 generic_args: &[],
             });
@@ -58,43 +62,131 @@ impl_number!(u8, U8);
 impl_number!(u16, U16);
 impl_number!(u32, U32);
 impl_number!(u64, U64);
+impl_number!(u128, U128);
 impl_number!(usize, Usize);
 impl_number!(i8, I8);
 impl_number!(i16, I16);
 impl_number!(i32, I32);
 impl_number!(i64, I64);
+impl_number!(i128, I128);
 impl_number!(isize, Isize);
 impl_number!(std::num::NonZeroU8, NonZeroU8);
 impl_number!(std::num::NonZeroU16, NonZeroU16);
 impl_number!(std::num::NonZeroU32, NonZeroU32);
 impl_number!(std::num::NonZeroU64, NonZeroU64);
+impl_number!(std::num::NonZeroU128, NonZeroU128);
 impl_number!(std::num::NonZeroUsize, NonZeroUsize);
 impl_number!(std::num::NonZeroI8, NonZeroI8);
 impl_number!(std::num::NonZeroI16, NonZeroI16);
 impl_number!(std::num::NonZeroI32, NonZeroI32);
 impl_number!(std::num::NonZeroI64, NonZeroI64);
+impl_number!(std::num::NonZeroI128, NonZeroI128);
 impl_number!(std::num::NonZeroIsize, NonZeroIsize);
 impl_number!(f32, F32);
 impl_number!(f64, F64);
 
+macro_rules! impl_atomic {
+    ($ty:ident, $inner:ty) => {
+        impl TypeDef for std::sync::atomic::$ty {
+            const INFO: TypeInfo = <$inner>::INFO;
+        }
+    };
+}
+
+#[cfg(target_has_atomic = "8")]
+impl_atomic!(AtomicBool, bool);
+#[cfg(target_has_atomic = "8")]
+impl_atomic!(AtomicU8, u8);
+#[cfg(target_has_atomic = "16")]
+impl_atomic!(AtomicU16, u16);
+#[cfg(target_has_atomic = "32")]
+impl_atomic!(AtomicU32, u32);
+#[cfg(target_has_atomic = "64")]
+impl_atomic!(AtomicU64, u64);
+#[cfg(target_has_atomic = "ptr")]
+impl_atomic!(AtomicUsize, usize);
+#[cfg(target_has_atomic = "8")]
+impl_atomic!(AtomicI8, i8);
+#[cfg(target_has_atomic = "16")]
+impl_atomic!(AtomicI16, i16);
+#[cfg(target_has_atomic = "32")]
+impl_atomic!(AtomicI32, i32);
+#[cfg(target_has_atomic = "64")]
+impl_atomic!(AtomicI64, i64);
+#[cfg(target_has_atomic = "ptr")]
+impl_atomic!(AtomicIsize, isize);
+
+macro_rules! alias_type_info {
+    ($name:literal, $def:expr, $docs:literal) => {
+        alias_type_info!([], $name, $def, $docs)
+    };
+    ([$($path:literal),*], $name:literal, $def:expr, $docs:literal) => {
+        TypeInfo::Defined(DefinedTypeInfo {
+            def: TypeDefinition {
+                docs: Some(crate::type_expr::Docs($docs)),
+                path: &[$(Ident($path)),*],
+                name: Ident($name),
+                generic_vars: &[],
+                def: $def,
+                enum_values: None,
+                brand: false,
+                std_number: false,
+            },
+            generic_args: &[],
+        })
+    };
+}
+
+macro_rules! impl_alias {
+    ($ty:ty, [$($path:literal),*], $name:literal, $def:expr, $docs:literal) => {
+        impl TypeDef for $ty {
+            const INFO: TypeInfo =
+                alias_type_info!([$($path),*], $name, $def, $docs);
+        }
+    };
+    ($ty:ty, $name:literal, $def:expr, $docs:literal) => {
+        impl_alias!($ty, [], $name, $def, $docs);
+    };
+}
+
+#[allow(unused_macros)] // only used by optional features
+macro_rules! string_union {
+    ($($value:literal),+ $(,)?) => {
+        TypeExpr::Union(TypeUnion {
+            docs: None,
+            members: &[$(TypeExpr::String(TypeString {
+                docs: None,
+                value: $value,
+            }),)+],
+        })
+    };
+}
+
 impl TypeDef for () {
     const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
         r#ref: TypeExpr::ident(Ident("null")),
     });
 }
 
+macro_rules! tuple_type_info {
+    ($info:ident, $($var:ident),+) => {
+        TypeInfo::Native(NativeTypeInfo {
+            r#ref: TypeExpr::Tuple(TypeTuple {
+                docs: None,
+                elements: &[$(TypeExpr::Ref(&$var::$info),)+],
+            }),
+        })
+    };
+}
+
 macro_rules! impl_tuple {
     ($($var:ident),+) => {
         impl<$($var),+> TypeDef for ($($var,)+)
         where
             $($var: TypeDef,)+
         {
-            const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
-                r#ref: TypeExpr::Tuple(TypeTuple {
-                    docs: None,
-                    elements: &[$(TypeExpr::Ref(&$var::INFO),)+],
-                }),
-            });
+            const INFO: TypeInfo = tuple_type_info!(INFO, $($var),+);
+            const INFO_DE: TypeInfo = tuple_type_info!(INFO_DE, $($var),+);
         }
     };
 }
@@ -128,6 +220,12 @@ where
             elements: &[TypeExpr::Ref(&T::INFO); N],
         }),
     });
+    const INFO_DE: TypeInfo = TypeInfo::Native(NativeTypeInfo {
+        r#ref: TypeExpr::Tuple(TypeTuple {
+            docs: None,
+            elements: &[TypeExpr::Ref(&T::INFO_DE); N],
+        }),
+    });
 }
 
 impl<T> TypeDef for Option<T>
@@ -140,14 +238,23 @@ where
             members: &[TypeExpr::Ref(&T::INFO), TypeExpr::ident(Ident("null"))],
         }),
     });
+    const INFO_DE: TypeInfo = TypeInfo::Native(NativeTypeInfo {
+        r#ref: TypeExpr::Union(TypeUnion {
+            docs: None,
+            members: &[
+                TypeExpr::Ref(&T::INFO_DE),
+                TypeExpr::ident(Ident("null")),
+            ],
+        }),
+    });
 }
 
 macro_rules! list_type_info {
-    ($item:ty) => {
+    ($item:ty, $info:ident) => {
         TypeInfo::Native(NativeTypeInfo {
             r#ref: TypeExpr::Array(TypeArray {
                 docs: None,
-                item: &TypeExpr::Ref(&<$item>::INFO),
+                item: &TypeExpr::Ref(&<$item>::$info),
             }),
         })
     };
@@ -157,22 +264,48 @@ impl<T> TypeDef for Vec<T>
 where
     T: TypeDef,
 {
-    const INFO: TypeInfo = list_type_info!(T);
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
 }
 
 impl<T> TypeDef for [T]
 where
     T: TypeDef,
 {
-    const INFO: TypeInfo = list_type_info!(T);
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::collections::VecDeque<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::collections::LinkedList<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::collections::BinaryHeap<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
 }
 
 macro_rules! set_type_info {
-    ($item:ty) => {
+    ($item:ty, $info:ident) => {
         TypeInfo::Native(NativeTypeInfo {
             r#ref: TypeExpr::Array(TypeArray {
                 docs: None,
-                item: &TypeExpr::Ref(&<$item>::INFO),
+                item: &TypeExpr::Ref(&<$item>::$info),
             }),
         })
     };
@@ -183,25 +316,27 @@ where
     T: TypeDef,
     S: 'static,
 {
-    const INFO: TypeInfo = set_type_info!(T);
+    const INFO: TypeInfo = set_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = set_type_info!(T, INFO_DE);
 }
 
 impl<T> TypeDef for std::collections::BTreeSet<T>
 where
     T: TypeDef,
 {
-    const INFO: TypeInfo = set_type_info!(T);
+    const INFO: TypeInfo = set_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = set_type_info!(T, INFO_DE);
 }
 
 macro_rules! map_type_info {
-    ($key:ty, $value:ty) => {
+    ($key:ty, $value:ty, $info:ident) => {
         TypeInfo::Native(NativeTypeInfo {
             r#ref: TypeExpr::Name(TypeName {
                 path: &[],
                 name: Ident("Record"),
                 generic_args: &[
-                    TypeExpr::Ref(&<$key>::INFO),
-                    TypeExpr::Ref(&<$value>::INFO),
+                    TypeExpr::Ref(&<$key>::$info),
+                    TypeExpr::Ref(&<$value>::$info),
                 ],
             }),
         })
@@ -214,7 +349,8 @@ where
     V: TypeDef,
     S: 'static,
 {
-    const INFO: TypeInfo = map_type_info!(K, V);
+    const INFO: TypeInfo = map_type_info!(K, V, INFO);
+    const INFO_DE: TypeInfo = map_type_info!(K, V, INFO_DE);
 }
 
 impl<K, V> TypeDef for std::collections::BTreeMap<K, V>
@@ -222,7 +358,8 @@ where
     K: TypeDef,
     V: TypeDef,
 {
-    const INFO: TypeInfo = map_type_info!(K, V);
+    const INFO: TypeInfo = map_type_info!(K, V, INFO);
+    const INFO_DE: TypeInfo = map_type_info!(K, V, INFO_DE);
 }
 
 #[cfg(feature = "json_value")]
@@ -231,43 +368,177 @@ where
     K: TypeDef,
     V: TypeDef,
 {
-    const INFO: TypeInfo = map_type_info!(K, V);
+    const INFO: TypeInfo = map_type_info!(K, V, INFO);
+    const INFO_DE: TypeInfo = map_type_info!(K, V, INFO_DE);
+}
+
+macro_rules! wrapper_type_info {
+    ($inner:ty, $info:ident) => {
+        TypeInfo::Native(NativeTypeInfo {
+            r#ref: TypeExpr::Ref(&<$inner>::$info),
+        })
+    };
 }
 
 impl<T> TypeDef for &'static T
 where
     T: TypeDef + ?Sized,
 {
-    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
-        r#ref: TypeExpr::Ref(&T::INFO),
-    });
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for &'static mut T
+where
+    T: TypeDef + ?Sized,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
 }
 
 impl<T> TypeDef for Box<T>
 where
     T: TypeDef + ?Sized,
 {
-    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
-        r#ref: TypeExpr::Ref(&T::INFO),
-    });
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::rc::Rc<T>
+where
+    T: TypeDef + ?Sized,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::sync::Arc<T>
+where
+    T: TypeDef + ?Sized,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+// weak pointers are serialized as `None` if they have been dropped
+impl<T> TypeDef for std::rc::Weak<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(Option<T>, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(Option<T>, INFO_DE);
+}
+
+impl<T> TypeDef for std::sync::Weak<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(Option<T>, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(Option<T>, INFO_DE);
 }
 
 impl<T> TypeDef for std::borrow::Cow<'static, T>
 where
-    T: Clone + TypeDef + ?Sized,
+    T: ToOwned + TypeDef + ?Sized,
 {
-    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
-        r#ref: TypeExpr::Ref(&T::INFO),
-    });
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
 }
 
-impl<T> TypeDef for std::marker::PhantomData<T>
+impl<T> TypeDef for std::cell::Cell<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::cell::RefCell<T>
 where
     T: TypeDef + ?Sized,
 {
-    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
-        r#ref: TypeExpr::Ref(&T::INFO),
-    });
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::sync::Mutex<T>
+where
+    T: TypeDef + ?Sized,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::sync::RwLock<T>
+where
+    T: TypeDef + ?Sized,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::cmp::Reverse<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+impl<T> TypeDef for std::num::Wrapping<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+// serde serializes `PhantomData` as unit
+impl<T> TypeDef for std::marker::PhantomData<T>
+where
+    T: ?Sized + 'static,
+{
+    const INFO: TypeInfo = <()>::INFO;
+}
+
+macro_rules! result_type_info {
+    ($ok:ty, $err:ty, $info:ident) => {
+        TypeInfo::Native(NativeTypeInfo {
+            r#ref: TypeExpr::Union(TypeUnion {
+                docs: None,
+                members: &[
+                    TypeExpr::Object(TypeObject {
+                        docs: None,
+                        index_signature: None,
+                        fields: &[ObjectField {
+                            docs: None,
+                            name: TypeString {
+                                docs: None,
+                                value: "Ok",
+                            },
+                            optional: false,
+                            r#type: TypeExpr::Ref(&<$ok>::$info),
+                        }],
+                        deny_unknown_fields: false,
+                    }),
+                    TypeExpr::Object(TypeObject {
+                        docs: None,
+                        index_signature: None,
+                        fields: &[ObjectField {
+                            docs: None,
+                            name: TypeString {
+                                docs: None,
+                                value: "Err",
+                            },
+                            optional: false,
+                            r#type: TypeExpr::Ref(&<$err>::$info),
+                        }],
+                        deny_unknown_fields: false,
+                    }),
+                ],
+            }),
+        })
+    };
 }
 
 impl<T, E> TypeDef for std::result::Result<T, E>
@@ -275,41 +546,210 @@ where
     T: TypeDef,
     E: TypeDef,
 {
-    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
-        r#ref: TypeExpr::Union(TypeUnion {
+    const INFO: TypeInfo = result_type_info!(T, E, INFO);
+    const INFO_DE: TypeInfo = result_type_info!(T, E, INFO_DE);
+}
+
+macro_rules! object_type_expr {
+    ($($field:literal: $type:expr),+ $(,)?) => {
+        TypeExpr::Object(TypeObject {
             docs: None,
-            members: &[
-                TypeExpr::Object(TypeObject {
+            index_signature: None,
+            fields: &[$(ObjectField {
+                docs: None,
+                name: TypeString {
                     docs: None,
-                    index_signature: None,
-                    fields: &[ObjectField {
-                        docs: None,
-                        name: TypeString {
-                            docs: None,
-                            value: "Ok",
-                        },
-                        optional: false,
-                        r#type: TypeExpr::Ref(&T::INFO),
-                    }],
-                }),
-                TypeExpr::Object(TypeObject {
+                    value: $field,
+                },
+                optional: false,
+                r#type: $type,
+            },)+],
+            deny_unknown_fields: false,
+        })
+    };
+}
+
+macro_rules! generic_type_info {
+    (
+        [$($path:literal),*],
+        $name:literal,
+        $var:literal,
+        $arg:ty,
+        $info:ident,
+        $def:expr,
+        $docs:literal
+    ) => {
+        TypeInfo::Defined(DefinedTypeInfo {
+            def: TypeDefinition {
+                docs: Some(crate::type_expr::Docs($docs)),
+                path: &[$(Ident($path)),*],
+                name: Ident($name),
+                generic_vars: &[Ident($var)],
+                def: $def,
+                enum_values: None,
+                brand: false,
+                std_number: false,
+            },
+            generic_args: &[TypeExpr::Ref(&<$arg>::$info)],
+        })
+    };
+}
+
+impl_alias!(
+    std::time::Duration,
+    ["std"],
+    "Duration",
+    object_type_expr!(
+        "secs": TypeExpr::Ref(&u64::INFO),
+        "nanos": TypeExpr::Ref(&u32::INFO),
+    ),
+    "A duration as whole seconds and nanoseconds, e.g. \
+    `{ \"secs\": 90, \"nanos\": 500000000 }` for 90.5 seconds."
+);
+impl_alias!(
+    std::time::SystemTime,
+    ["std"],
+    "SystemTime",
+    object_type_expr!(
+        "secs_since_epoch": TypeExpr::Ref(&u64::INFO),
+        "nanos_since_epoch": TypeExpr::Ref(&u32::INFO),
+    ),
+    "A point in time as whole seconds and nanoseconds since the Unix epoch, \
+    e.g. `{ \"secs_since_epoch\": 1704164645, \"nanos_since_epoch\": 0 }`."
+);
+
+macro_rules! impl_range {
+    ($ty:ident, $name:literal, [$($field:literal),+], $docs:literal) => {
+        impl<Idx> TypeDef for std::ops::$ty<Idx>
+        where
+            Idx: TypeDef,
+        {
+            const INFO: TypeInfo = generic_type_info!(
+                ["std"],
+                $name,
+                "Idx",
+                Idx,
+                INFO,
+                object_type_expr!($($field: TypeExpr::ident(Ident("Idx")),)+),
+                $docs
+            );
+            const INFO_DE: TypeInfo = generic_type_info!(
+                ["std"],
+                $name,
+                "Idx",
+                Idx,
+                INFO_DE,
+                object_type_expr!($($field: TypeExpr::ident(Ident("Idx")),)+),
+                $docs
+            );
+        }
+    };
+}
+
+impl_range!(
+    Range,
+    "Range",
+    ["start", "end"],
+    "A half-open range containing `start` but not `end`."
+);
+impl_range!(
+    RangeInclusive,
+    "RangeInclusive",
+    ["start", "end"],
+    "A closed range containing both `start` and `end`."
+);
+impl_range!(
+    RangeFrom,
+    "RangeFrom",
+    ["start"],
+    "A range containing everything from `start` onwards."
+);
+impl_range!(
+    RangeTo,
+    "RangeTo",
+    ["end"],
+    "A range containing everything up to but not including `end`."
+);
+
+macro_rules! bound_type_expr {
+    () => {
+        TypeExpr::Union(TypeUnion {
+            docs: None,
+            members: &[
+                TypeExpr::String(TypeString {
                     docs: None,
-                    index_signature: None,
-                    fields: &[ObjectField {
-                        docs: None,
-                        name: TypeString {
-                            docs: None,
-                            value: "Err",
-                        },
-                        optional: false,
-                        r#type: TypeExpr::Ref(&E::INFO),
-                    }],
+                    value: "Unbounded",
                 }),
+                object_type_expr!("Included": TypeExpr::ident(Ident("T"))),
+                object_type_expr!("Excluded": TypeExpr::ident(Ident("T"))),
             ],
-        }),
-    });
+        })
+    };
+}
+
+impl<T> TypeDef for std::ops::Bound<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = generic_type_info!(
+        ["std"],
+        "Bound",
+        "T",
+        T,
+        INFO,
+        bound_type_expr!(),
+        "An endpoint of a range, e.g. `{ \"Included\": 1 }`, \
+        `{ \"Excluded\": 1 }` or `\"Unbounded\"`."
+    );
+    const INFO_DE: TypeInfo = generic_type_info!(
+        ["std"],
+        "Bound",
+        "T",
+        T,
+        INFO_DE,
+        bound_type_expr!(),
+        "An endpoint of a range, e.g. `{ \"Included\": 1 }`, \
+        `{ \"Excluded\": 1 }` or `\"Unbounded\"`."
+    );
 }
 
+impl_alias!(
+    std::net::Ipv4Addr,
+    ["std"],
+    "Ipv4Addr",
+    TypeExpr::ident(Ident("string")),
+    "An IPv4 address, e.g. `\"127.0.0.1\"`."
+);
+impl_alias!(
+    std::net::Ipv6Addr,
+    ["std"],
+    "Ipv6Addr",
+    TypeExpr::ident(Ident("string")),
+    "An IPv6 address, e.g. `\"::1\"`."
+);
+impl_alias!(
+    std::net::SocketAddr,
+    ["std"],
+    "SocketAddr",
+    TypeExpr::ident(Ident("string")),
+    "An IPv4 or IPv6 socket address, e.g. `\"127.0.0.1:8080\"` or \
+    `\"[::1]:8080\"`."
+);
+impl_alias!(
+    std::net::SocketAddrV4,
+    ["std"],
+    "SocketAddrV4",
+    TypeExpr::ident(Ident("string")),
+    "An IPv4 socket address, e.g. `\"127.0.0.1:8080\"`."
+);
+impl_alias!(
+    std::net::SocketAddrV6,
+    ["std"],
+    "SocketAddrV6",
+    TypeExpr::ident(Ident("string")),
+    "An IPv6 socket address, e.g. `\"[::1]:8080\"`."
+);
+
 #[cfg(feature = "json_value")]
 impl TypeDef for serde_json::Value {
     const INFO: TypeInfo = TypeInfo::Defined(DefinedTypeInfo {
@@ -339,10 +779,399 @@ impl TypeDef for serde_json::Value {
                             },
                         ),
                         fields: &[],
+                        deny_unknown_fields: false,
                     }),
                 ],
             }),
+            enum_values: None,
+            brand: false,
+            std_number: false,
         },
         generic_args: &[],
     });
 }
+
+#[cfg(feature = "chrono")]
+impl<Tz> TypeDef for chrono::DateTime<Tz>
+where
+    Tz: chrono::TimeZone + 'static,
+{
+    const INFO: TypeInfo = alias_type_info!(
+        ["chrono"],
+        "DateTime",
+        TypeExpr::ident(Ident("string")),
+        "An RFC 3339 date and time with a UTC offset, e.g. \
+        `\"2024-01-02T03:04:05.678+09:00\"` or `\"2024-01-02T03:04:05Z\"`."
+    );
+}
+
+#[cfg(feature = "chrono")]
+impl_alias!(
+    chrono::NaiveDateTime,
+    ["chrono"],
+    "NaiveDateTime",
+    TypeExpr::ident(Ident("string")),
+    "An ISO 8601 date and time without a UTC offset, e.g. \
+    `\"2024-01-02T03:04:05.678\"`."
+);
+#[cfg(feature = "chrono")]
+impl_alias!(
+    chrono::NaiveDate,
+    ["chrono"],
+    "NaiveDate",
+    TypeExpr::ident(Ident("string")),
+    "An ISO 8601 calendar date, e.g. `\"2024-01-02\"`."
+);
+#[cfg(feature = "chrono")]
+impl_alias!(
+    chrono::NaiveTime,
+    ["chrono"],
+    "NaiveTime",
+    TypeExpr::ident(Ident("string")),
+    "An ISO 8601 time of day, e.g. `\"03:04:05.678\"`."
+);
+#[cfg(feature = "chrono")]
+impl_alias!(
+    chrono::TimeDelta,
+    ["chrono"],
+    "TimeDelta",
+    TypeExpr::Tuple(TypeTuple {
+        docs: None,
+        elements: &[TypeExpr::Ref(&i64::INFO), TypeExpr::Ref(&i32::INFO)],
+    }),
+    "A duration as a tuple of whole seconds and nanoseconds, e.g. \
+    `[90, 500000000]` for 90.5 seconds."
+);
+#[cfg(feature = "chrono")]
+impl_alias!(
+    chrono::Weekday,
+    ["chrono"],
+    "Weekday",
+    string_union!("Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"),
+    "A day of the week."
+);
+#[cfg(feature = "chrono")]
+impl_alias!(
+    chrono::Month,
+    ["chrono"],
+    "Month",
+    string_union!(
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ),
+    "A month of the year."
+);
+
+#[cfg(feature = "time")]
+impl_alias!(
+    time::OffsetDateTime,
+    ["time"],
+    "OffsetDateTime",
+    TypeExpr::ident(Ident("string")),
+    "A date and time with a UTC offset, e.g. \
+    `\"2024-01-02 03:04:05.678 +09:00:00\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::PrimitiveDateTime,
+    ["time"],
+    "PrimitiveDateTime",
+    TypeExpr::ident(Ident("string")),
+    "A date and time without a UTC offset, e.g. \
+    `\"2024-01-02 03:04:05.678\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::UtcDateTime,
+    ["time"],
+    "UtcDateTime",
+    TypeExpr::ident(Ident("string")),
+    "A date and time in UTC, e.g. `\"2024-01-02 03:04:05.678\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::Date,
+    ["time"],
+    "Date",
+    TypeExpr::ident(Ident("string")),
+    "An ISO 8601 calendar date, e.g. `\"2024-01-02\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::Time,
+    ["time"],
+    "Time",
+    TypeExpr::ident(Ident("string")),
+    "An ISO 8601 time of day, e.g. `\"03:04:05.678\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::UtcOffset,
+    ["time"],
+    "UtcOffset",
+    TypeExpr::ident(Ident("string")),
+    "A UTC offset in hours, minutes and seconds, e.g. `\"+09:00:00\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::Duration,
+    ["time"],
+    "Duration",
+    TypeExpr::ident(Ident("string")),
+    "A duration in seconds with nanosecond precision, e.g. \
+    `\"90.500000000\"` or `\"-1.000000000\"`."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::Weekday,
+    ["time"],
+    "Weekday",
+    string_union!(
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ),
+    "A day of the week."
+);
+#[cfg(feature = "time")]
+impl_alias!(
+    time::Month,
+    ["time"],
+    "Month",
+    string_union!(
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ),
+    "A month of the year."
+);
+
+#[cfg(feature = "uuid")]
+impl_alias!(
+    uuid::Uuid,
+    "Uuid",
+    TypeExpr::ident(Ident("string")),
+    "A UUID as a hyphenated hexadecimal string, e.g. \
+    `\"67e55044-10b1-426f-9247-bb680e5fe0c8\"`."
+);
+
+#[cfg(feature = "ulid")]
+impl_alias!(
+    ulid::Ulid,
+    "Ulid",
+    TypeExpr::ident(Ident("string")),
+    "A ULID as a 26-character Crockford base32 string, e.g. \
+    `\"01ARZ3NDEKTSV4RRFFQ69G5FAV\"`."
+);
+
+#[cfg(feature = "bson")]
+impl TypeDef for bson::oid::ObjectId {
+    const INFO: TypeInfo = alias_type_info!(
+        "ObjectId",
+        TypeExpr::Object(TypeObject {
+            docs: None,
+            index_signature: None,
+            fields: &[ObjectField {
+                docs: None,
+                name: TypeString {
+                    docs: None,
+                    value: "$oid",
+                },
+                optional: false,
+                r#type: TypeExpr::ident(Ident("string")),
+            }],
+            deny_unknown_fields: false,
+        }),
+        "A BSON ObjectId in MongoDB Extended JSON format, e.g. \
+        `{ \"$oid\": \"507f1f77bcf86cd799439011\" }`."
+    );
+    // a plain hex string is also accepted when deserializing
+    const INFO_DE: TypeInfo = alias_type_info!(
+        "ObjectId",
+        TypeExpr::Union(TypeUnion {
+            docs: None,
+            members: &[
+                TypeExpr::ident(Ident("string")),
+                TypeExpr::Ref(&Self::INFO),
+            ],
+        }),
+        "A BSON ObjectId as a 24-character hexadecimal string or in MongoDB \
+        Extended JSON format, e.g. `\"507f1f77bcf86cd799439011\"` or \
+        `{ \"$oid\": \"507f1f77bcf86cd799439011\" }`."
+    );
+}
+
+#[cfg(all(feature = "rust_decimal", not(feature = "rust_decimal_float")))]
+impl_alias!(
+    rust_decimal::Decimal,
+    "Decimal",
+    TypeExpr::ident(Ident("string")),
+    "A decimal number as a string, e.g. `\"3.14\"`."
+);
+#[cfg(feature = "rust_decimal_float")]
+impl_alias!(
+    rust_decimal::Decimal,
+    "Decimal",
+    TypeExpr::ident(Ident("number")),
+    "A decimal number, e.g. `3.14`."
+);
+
+#[cfg(feature = "bigdecimal")]
+impl_alias!(
+    bigdecimal::BigDecimal,
+    "BigDecimal",
+    TypeExpr::ident(Ident("string")),
+    "An arbitrary-precision decimal number as a string, e.g. `\"3.14\"`."
+);
+
+#[cfg(feature = "num-bigint")]
+impl_alias!(
+    num_bigint::BigUint,
+    "BigUint",
+    TypeExpr::Array(TypeArray {
+        docs: None,
+        item: &TypeExpr::Ref(&u32::INFO),
+    }),
+    "An arbitrary-precision unsigned integer as its base 2^32 digits in \
+    little-endian order, e.g. `[1, 2]` for 8589934593."
+);
+#[cfg(feature = "num-bigint")]
+impl_alias!(
+    num_bigint::BigInt,
+    "BigInt",
+    TypeExpr::Tuple(TypeTuple {
+        docs: None,
+        elements: &[
+            TypeExpr::Ref(&i8::INFO),
+            TypeExpr::Ref(&num_bigint::BigUint::INFO),
+        ],
+    }),
+    "An arbitrary-precision integer as a tuple of its sign (`-1`, `0` or \
+    `1`) and magnitude, e.g. `[-1, [1, 2]]` for -8589934593."
+);
+
+#[cfg(feature = "ordered-float")]
+impl<T> TypeDef for ordered_float::OrderedFloat<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+#[cfg(feature = "ordered-float")]
+impl<T> TypeDef for ordered_float::NotNan<T>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = wrapper_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = wrapper_type_info!(T, INFO_DE);
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> TypeDef for indexmap::IndexMap<K, V, S>
+where
+    K: TypeDef,
+    V: TypeDef,
+    S: 'static,
+{
+    const INFO: TypeInfo = map_type_info!(K, V, INFO);
+    const INFO_DE: TypeInfo = map_type_info!(K, V, INFO_DE);
+}
+
+#[cfg(feature = "indexmap")]
+impl<T, S> TypeDef for indexmap::IndexSet<T, S>
+where
+    T: TypeDef,
+    S: 'static,
+{
+    const INFO: TypeInfo = set_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = set_type_info!(T, INFO_DE);
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> TypeDef for smallvec::SmallVec<A>
+where
+    A: smallvec::Array + 'static,
+    A::Item: TypeDef,
+{
+    const INFO: TypeInfo = list_type_info!(A::Item, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(A::Item, INFO_DE);
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const CAP: usize> TypeDef for arrayvec::ArrayVec<T, CAP>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> TypeDef for arrayvec::ArrayString<CAP> {
+    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
+        r#ref: TypeExpr::ident(Ident("string")),
+    });
+}
+
+// byte buffers are serialized as arrays of bytes, but can also be deserialized
+// from strings
+#[cfg(feature = "bytes")]
+impl TypeDef for bytes::Bytes {
+    const INFO: TypeInfo = list_type_info!(u8, INFO);
+    const INFO_DE: TypeInfo = TypeInfo::Native(NativeTypeInfo {
+        r#ref: TypeExpr::Union(TypeUnion {
+            docs: None,
+            members: &[
+                TypeExpr::Ref(&Self::INFO),
+                TypeExpr::ident(Ident("string")),
+            ],
+        }),
+    });
+}
+
+#[cfg(feature = "bytes")]
+impl TypeDef for bytes::BytesMut {
+    const INFO: TypeInfo = bytes::Bytes::INFO;
+    const INFO_DE: TypeInfo = bytes::Bytes::INFO_DE;
+}
+
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> TypeDef for heapless::Vec<T, N>
+where
+    T: TypeDef,
+{
+    const INFO: TypeInfo = list_type_info!(T, INFO);
+    const INFO_DE: TypeInfo = list_type_info!(T, INFO_DE);
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> TypeDef for heapless::String<N> {
+    const INFO: TypeInfo = TypeInfo::Native(NativeTypeInfo {
+        r#ref: TypeExpr::ident(Ident("string")),
+    });
+}
@@ -1,11 +1,12 @@
 use crate::type_expr::{
-    DefinedTypeInfo, Ident, IndexSignature, NativeTypeInfo, ObjectField,
-    TypeArray, TypeDefinition, TypeExpr, TypeInfo, TypeIntersection, TypeName,
-    TypeObject, TypeString, TypeTuple, TypeUnion,
+    DefinedTypeInfo, EnumMember, EnumValues, Ident, IndexSignature,
+    NativeTypeInfo, ObjectField, TypeArray, TypeDefinition, TypeExpr, TypeInfo,
+    TypeIntersection, TypeName, TypeObject, TypeRaw, TypeString, TypeTuple,
+    TypeUnion,
 };
 use std::{
-    collections::HashSet,
-    hash::Hash,
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
     iter::{self, FusedIterator},
     slice,
 };
@@ -13,10 +14,13 @@ use std::{
 /// An iterator which produces all type definitions that a type depends on.
 ///
 /// Type definitions dependencies (including those of generic types) are
-/// produced exactly once in post-order.
+/// produced exactly once in post-order. Recursive types are supported by
+/// treating a type whose dependencies are currently being visited as already
+/// visited, so cycles are only traversed once.
 pub struct IterDefDeps {
     stack: Vec<TypeExpr>,
     visited: HashSet<u64>,
+    in_progress: HashSet<u64>,
     emitted: HashSet<u64>,
 }
 
@@ -27,6 +31,7 @@ impl IterDefDeps {
             // reverse order so they are popped from the stack in original order
             stack: roots.iter().rev().map(|x| TypeExpr::Ref(x)).collect(),
             visited: HashSet::new(),
+            in_progress: HashSet::new(),
             emitted: HashSet::new(),
         }
     }
@@ -39,35 +44,37 @@ impl Iterator for IterDefDeps {
         let Self {
             stack,
             visited,
+            in_progress,
             emitted,
         } = self;
         while let Some(expr) = stack.pop() {
-            if TypeExprChildren::new(&expr).all(|child| {
-                visited.contains(&hash_type_expr(child, HashKind::Visit))
-            }) {
-                let expr_visit_hash = hash_type_expr(&expr, HashKind::Visit);
+            let expr_visit_hash = hash_type_expr(&expr, HashKind::Visit);
+            if TypeExprChildren::new(&expr)
+                .all(|child| is_visited(child, visited, in_progress))
+            {
                 let expr_emit_hash = hash_type_expr(&expr, HashKind::Emit);
+                in_progress.remove(&expr_visit_hash);
+                visited.insert(expr_visit_hash);
                 if !emitted.contains(&expr_emit_hash) {
                     emitted.insert(expr_emit_hash);
-                    if let TypeExpr::Ref(TypeInfo::Defined(DefinedTypeInfo {
+                    if let Some(TypeInfo::Defined(DefinedTypeInfo {
                         def,
                         generic_args: _,
-                    })) = expr
+                    })) = ref_type_info(&expr)
                     {
                         return Some(def);
                     }
                 }
-                visited.insert(expr_visit_hash);
             } else {
+                // only definitions can be recursive, and other expressions may
+                // share the hash of their children
+                if let Some(TypeInfo::Defined(_)) = ref_type_info(&expr) {
+                    in_progress.insert(expr_visit_hash);
+                }
                 stack.push(expr);
                 stack.extend(
                     TypeExprChildren::new(&expr)
-                        .filter(|expr| {
-                            !visited.contains(&hash_type_expr(
-                                expr,
-                                HashKind::Visit,
-                            ))
-                        })
+                        .filter(|expr| !is_visited(expr, visited, in_progress))
                         .rev(),
                 );
             }
@@ -78,6 +85,15 @@ impl Iterator for IterDefDeps {
 
 impl FusedIterator for IterDefDeps {}
 
+fn is_visited(
+    expr: &TypeExpr,
+    visited: &HashSet<u64>,
+    in_progress: &HashSet<u64>,
+) -> bool {
+    let hash = hash_type_expr(expr, HashKind::Visit);
+    visited.contains(&hash) || in_progress.contains(&hash)
+}
+
 /// An iterator which produces all of the direct type expression children of a
 /// type expression.
 enum TypeExprChildren<'a> {
@@ -93,22 +109,8 @@ enum TypeExprChildren<'a> {
 impl<'a> TypeExprChildren<'a> {
     fn new(expr: &'a TypeExpr) -> Self {
         match expr {
-            TypeExpr::Ref(TypeInfo::Native(NativeTypeInfo { r#ref })) => {
-                Self::One(iter::once(r#ref))
-            }
-            TypeExpr::Ref(TypeInfo::Defined(DefinedTypeInfo {
-                def:
-                    TypeDefinition {
-                        docs: _,
-                        path: _,
-                        name: _,
-                        generic_vars: _,
-                        def,
-                    },
-                generic_args,
-            })) => {
-                Self::OneThenSlice(iter::once(def).chain(generic_args.iter()))
-            }
+            TypeExpr::Ref(type_info) => Self::from_type_info(type_info),
+            TypeExpr::LazyRef(type_info) => Self::from_type_info(type_info()),
             TypeExpr::Name(TypeName {
                 path: _,
                 name: _,
@@ -122,6 +124,7 @@ impl<'a> TypeExprChildren<'a> {
                 docs: _,
                 index_signature,
                 fields,
+                deny_unknown_fields: _,
             }) => Self::Object(index_signature.as_ref(), fields.iter()),
             TypeExpr::Array(TypeArray { docs: _, item }) => {
                 Self::One(iter::once(item))
@@ -132,6 +135,31 @@ impl<'a> TypeExprChildren<'a> {
             TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
                 Self::Slice(members.iter())
             }
+            TypeExpr::Raw(TypeRaw { ts: _, deps }) => Self::Slice(deps.iter()),
+        }
+    }
+
+    fn from_type_info(type_info: &'a TypeInfo) -> Self {
+        match type_info {
+            TypeInfo::Native(NativeTypeInfo { r#ref }) => {
+                Self::One(iter::once(r#ref))
+            }
+            TypeInfo::Defined(DefinedTypeInfo {
+                def:
+                    TypeDefinition {
+                        docs: _,
+                        path: _,
+                        name: _,
+                        generic_vars: _,
+                        def,
+                        enum_values: _,
+                        brand: _,
+                        std_number: _,
+                    },
+                generic_args,
+            }) => {
+                Self::OneThenSlice(iter::once(def).chain(generic_args.iter()))
+            }
         }
     }
 }
@@ -228,114 +256,200 @@ enum HashKind {
     Emit,
 }
 
+/// Returns the type info referenced by a type expression, if any.
+pub(crate) fn ref_type_info(expr: &TypeExpr) -> Option<&'static TypeInfo> {
+    match expr {
+        TypeExpr::Ref(type_info) => Some(type_info),
+        TypeExpr::LazyRef(type_info) => Some(type_info()),
+        _ => None,
+    }
+}
+
 fn hash_type_expr(expr: &TypeExpr, hash_kind: HashKind) -> u64 {
-    use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+    let mut hasher = DefaultHasher::new();
+    visit_expr(expr, hash_kind, &mut hasher, &mut Vec::new());
+    hasher.finish()
+}
 
-    fn visit_expr(
-        expr: &TypeExpr,
-        hash_kind: HashKind,
-        state: &mut DefaultHasher,
-    ) {
-        match expr {
-            TypeExpr::Ref(TypeInfo::Native(NativeTypeInfo { r#ref })) => {
-                visit_expr(r#ref, hash_kind, state);
-            }
-            TypeExpr::Ref(TypeInfo::Defined(DefinedTypeInfo {
-                def:
-                    TypeDefinition {
-                        docs: _,
-                        path,
-                        name: Ident(name),
-                        generic_vars,
-                        def,
-                    },
-                generic_args,
-            })) => {
-                for Ident(path_part) in *path {
-                    path_part.hash(state);
-                }
-                name.hash(state);
-                for Ident(generic_var) in *generic_vars {
-                    generic_var.hash(state);
-                }
-                visit_expr(def, hash_kind, state);
-                match hash_kind {
-                    HashKind::Visit => {
-                        for generic_arg in *generic_args {
-                            visit_expr(generic_arg, hash_kind, state);
-                        }
+/// Returns a hash which identifies a type definition.
+///
+/// This is the same hash that is used to de-duplicate emitted definitions, so
+/// two definitions produced by [`IterDefDeps`] always have distinct hashes.
+pub(crate) fn hash_type_definition(type_def: &'static TypeDefinition) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    visit_type_def(type_def, HashKind::Emit, &mut hasher, &mut Vec::new());
+    hasher.finish()
+}
+
+/// The stack of type definitions currently being hashed, used to detect
+/// recursive types.
+///
+/// Since a definition is invariant of its generic arguments, encountering
+/// a definition with the same path and name while it is already being
+/// hashed means the type refers to itself.
+type DefStack = Vec<&'static TypeDefinition>;
+
+fn is_same_def(a: &TypeDefinition, b: &TypeDefinition) -> bool {
+    a.name.0 == b.name.0
+        && a.path.len() == b.path.len()
+        && a.path.iter().zip(b.path).all(|(a, b)| a.0 == b.0)
+}
+
+fn visit_type_info(
+    type_info: &'static TypeInfo,
+    hash_kind: HashKind,
+    state: &mut DefaultHasher,
+    def_stack: &mut DefStack,
+) {
+    match type_info {
+        TypeInfo::Native(NativeTypeInfo { r#ref }) => {
+            visit_expr(r#ref, hash_kind, state, def_stack);
+        }
+        TypeInfo::Defined(DefinedTypeInfo { def, generic_args }) => {
+            visit_type_def(def, hash_kind, state, def_stack);
+            match hash_kind {
+                HashKind::Visit => {
+                    for generic_arg in *generic_args {
+                        visit_expr(generic_arg, hash_kind, state, def_stack);
                     }
-                    HashKind::Emit => {}
                 }
+                HashKind::Emit => {}
             }
-            TypeExpr::Name(TypeName {
-                path,
-                name: Ident(name),
-                generic_args,
-            }) => {
-                for Ident(path_part) in *path {
-                    path_part.hash(state);
-                }
-                name.hash(state);
-                for generic_arg in *generic_args {
-                    visit_expr(generic_arg, hash_kind, state);
-                }
+        }
+    }
+}
+
+fn visit_type_def(
+    type_def: &'static TypeDefinition,
+    hash_kind: HashKind,
+    state: &mut DefaultHasher,
+    def_stack: &mut DefStack,
+) {
+    let TypeDefinition {
+        docs: _,
+        path,
+        name: Ident(name),
+        generic_vars,
+        def,
+        enum_values,
+        brand,
+        std_number,
+    } = type_def;
+    for Ident(path_part) in *path {
+        path_part.hash(state);
+    }
+    name.hash(state);
+    for Ident(generic_var) in *generic_vars {
+        generic_var.hash(state);
+    }
+    if let Some(EnumValues { style, members }) = enum_values {
+        style.hash(state);
+        for EnumMember {
+            docs: _,
+            name: Ident(name),
+            value,
+        } in *members
+        {
+            name.hash(state);
+            value.hash(state);
+        }
+    }
+    brand.hash(state);
+    std_number.hash(state);
+    let is_recursive =
+        def_stack.iter().any(|other| is_same_def(other, type_def));
+    is_recursive.hash(state);
+    if !is_recursive {
+        def_stack.push(type_def);
+        visit_expr(def, hash_kind, state, def_stack);
+        def_stack.pop();
+    }
+}
+
+fn visit_expr(
+    expr: &TypeExpr,
+    hash_kind: HashKind,
+    state: &mut DefaultHasher,
+    def_stack: &mut DefStack,
+) {
+    match expr {
+        TypeExpr::Ref(type_info) => {
+            visit_type_info(type_info, hash_kind, state, def_stack);
+        }
+        TypeExpr::LazyRef(type_info) => {
+            visit_type_info(type_info(), hash_kind, state, def_stack);
+        }
+        TypeExpr::Name(TypeName {
+            path,
+            name: Ident(name),
+            generic_args,
+        }) => {
+            for Ident(path_part) in *path {
+                path_part.hash(state);
             }
-            TypeExpr::String(TypeString { docs: _, value }) => {
-                value.hash(state);
+            name.hash(state);
+            for generic_arg in *generic_args {
+                visit_expr(generic_arg, hash_kind, state, def_stack);
             }
-            TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
-                for element in *elements {
-                    visit_expr(element, hash_kind, state);
-                }
+        }
+        TypeExpr::String(TypeString { docs: _, value }) => {
+            value.hash(state);
+        }
+        TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
+            for element in *elements {
+                visit_expr(element, hash_kind, state, def_stack);
             }
-            TypeExpr::Object(TypeObject {
+        }
+        TypeExpr::Object(TypeObject {
+            docs: _,
+            index_signature,
+            fields,
+            deny_unknown_fields,
+        }) => {
+            deny_unknown_fields.hash(state);
+            if let Some(IndexSignature {
                 docs: _,
-                index_signature,
-                fields,
-            }) => {
-                if let Some(IndexSignature {
-                    docs: _,
-                    name: Ident(name),
-                    value,
-                }) = index_signature
-                {
-                    name.hash(state);
-                    visit_expr(value, hash_kind, state);
-                }
-                for ObjectField {
-                    docs: _,
-                    name:
-                        TypeString {
-                            docs: _,
-                            value: name,
-                        },
-                    optional,
-                    r#type,
-                } in *fields
-                {
-                    name.hash(state);
-                    optional.hash(state);
-                    visit_expr(r#type, hash_kind, state);
-                }
+                name: Ident(name),
+                value,
+            }) = index_signature
+            {
+                name.hash(state);
+                visit_expr(value, hash_kind, state, def_stack);
             }
-            TypeExpr::Array(TypeArray { docs: _, item }) => {
-                visit_expr(item, hash_kind, state);
+            for ObjectField {
+                docs: _,
+                name:
+                    TypeString {
+                        docs: _,
+                        value: name,
+                    },
+                optional,
+                r#type,
+            } in *fields
+            {
+                name.hash(state);
+                optional.hash(state);
+                visit_expr(r#type, hash_kind, state, def_stack);
             }
-            TypeExpr::Union(TypeUnion { docs: _, members }) => {
-                for member in *members {
-                    visit_expr(member, hash_kind, state);
-                }
+        }
+        TypeExpr::Array(TypeArray { docs: _, item }) => {
+            visit_expr(item, hash_kind, state, def_stack);
+        }
+        TypeExpr::Union(TypeUnion { docs: _, members }) => {
+            for member in *members {
+                visit_expr(member, hash_kind, state, def_stack);
             }
-            TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
-                for member in *members {
-                    visit_expr(member, hash_kind, state);
-                }
+        }
+        TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+            for member in *members {
+                visit_expr(member, hash_kind, state, def_stack);
+            }
+        }
+        TypeExpr::Raw(TypeRaw { ts, deps }) => {
+            ts.hash(state);
+            for dep in *deps {
+                visit_expr(dep, hash_kind, state, def_stack);
             }
         }
     }
-
-    let mut hasher = DefaultHasher::new();
-    visit_expr(expr, hash_kind, &mut hasher);
-    hasher.finish()
 }
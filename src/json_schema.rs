@@ -0,0 +1,960 @@
+use crate::{
+    emit::{
+        DefNames, IntegerRepr, JsString, ModuleStyle, NameConflicts, Stats,
+        TypeDef,
+    },
+    iter_def_deps::{hash_type_definition, ref_type_info, IterDefDeps},
+    type_expr::{
+        without_empty_object, DefinedTypeInfo, Docs, Ident, IndexSignature,
+        NativeTypeInfo, ObjectField, TypeArray, TypeDefinition, TypeExpr,
+        TypeInfo, TypeIntersection, TypeName, TypeObject, TypeRaw, TypeString,
+        TypeTuple, TypeUnion,
+    },
+    type_guards::IntegerBounds,
+    zod::discriminator,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    io, mem,
+};
+
+/// Options for customizing the output of [`write_json_schema`].
+///
+/// The default options are:
+/// ```
+/// # use typescript_type_def::{
+/// #     IntegerRepr, JsonSchemaOptions, NameConflicts,
+/// # };
+/// # let default =
+/// JsonSchemaOptions {
+///     id: None,
+///     name_conflicts: NameConflicts::Error,
+///     int64_repr: IntegerRepr::Number,
+///     int128_repr: IntegerRepr::String,
+/// }
+/// # ;
+/// # assert_eq!(default, Default::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonSchemaOptions<'a> {
+    /// The URI to use as the `$id` of the schema document.
+    ///
+    /// If `None`, no `$id` will be added.
+    pub id: Option<&'a str>,
+    /// How to handle distinct type definitions which have the same name and
+    /// namespace.
+    pub name_conflicts: NameConflicts,
+    /// How the aliases of 64-bit integer types are encoded.
+    ///
+    /// Integers represented as [`IntegerRepr::String`] are described as
+    /// strings of decimal digits. Since JSON has no `bigint`,
+    /// [`IntegerRepr::BigInt`] is described the same as
+    /// [`IntegerRepr::Number`]. See the
+    /// [`int64_repr`](crate::DefinitionFileOptions::int64_repr) option of
+    /// [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+    pub int64_repr: IntegerRepr,
+    /// How the aliases of 128-bit integer types are encoded.
+    ///
+    /// See [`int64_repr`](Self::int64_repr).
+    pub int128_repr: IntegerRepr,
+}
+
+impl Default for JsonSchemaOptions<'_> {
+    fn default() -> Self {
+        Self {
+            id: None,
+            name_conflicts: NameConflicts::Error,
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+        }
+    }
+}
+
+/// The URI of the JSON Schema dialect of the produced documents.
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A JSON value which preserves the order of object entries.
+#[derive(Clone)]
+enum Json {
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn object<I, K>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, Json)>,
+        K: Into<String>,
+    {
+        Self::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    fn string(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+
+    fn type_(name: &str) -> Self {
+        Self::object([("type", Self::string(name))])
+    }
+
+    /// Adds a description to a schema from doc comments.
+    fn with_docs(self, docs: &Option<Docs>) -> Self {
+        match (self, docs) {
+            (Self::Object(mut entries), Some(Docs(docs))) => {
+                entries
+                    .insert(0, ("description".to_owned(), Self::string(docs)));
+                Self::Object(entries)
+            }
+            (json, _) => json,
+        }
+    }
+
+    fn write(&self, w: &mut dyn io::Write, indent: usize) -> io::Result<()> {
+        let write_indent = |w: &mut dyn io::Write, indent: usize| {
+            write!(w, "{}", "  ".repeat(indent))
+        };
+        match self {
+            Self::Bool(value) => write!(w, "{}", value),
+            Self::Number(value) => write!(w, "{}", value),
+            Self::String(value) => write_json_string(w, value),
+            Self::Array(items) if items.is_empty() => write!(w, "[]"),
+            Self::Array(items) => {
+                writeln!(w, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    write_indent(w, indent + 1)?;
+                    item.write(w, indent + 1)?;
+                    if i + 1 < items.len() {
+                        write!(w, ",")?;
+                    }
+                    writeln!(w)?;
+                }
+                write_indent(w, indent)?;
+                write!(w, "]")
+            }
+            Self::Object(entries) if entries.is_empty() => write!(w, "{{}}"),
+            Self::Object(entries) => {
+                writeln!(w, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    write_indent(w, indent + 1)?;
+                    write_json_string(w, key)?;
+                    write!(w, ": ")?;
+                    value.write(w, indent + 1)?;
+                    if i + 1 < entries.len() {
+                        write!(w, ",")?;
+                    }
+                    writeln!(w)?;
+                }
+                write_indent(w, indent)?;
+                write!(w, "}}")
+            }
+        }
+    }
+}
+
+fn write_json_string(w: &mut dyn io::Write, value: &str) -> io::Result<()> {
+    write!(w, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            '\r' => write!(w, "\\r")?,
+            '\t' => write!(w, "\\t")?,
+            c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => write!(w, "{}", c)?,
+        }
+    }
+    write!(w, "\"")
+}
+
+/// Returns the `$ref` URI of the definition with the given key in `$defs`.
+fn def_ref(key: &str) -> String {
+    let mut uri = "#/$defs/".to_owned();
+    for c in key.chars() {
+        match c {
+            // JSON pointer escapes
+            '~' => uri.push_str("~0"),
+            '/' => uri.push_str("~1"),
+            // characters which are allowed in URI fragments
+            'A'..='Z'
+            | 'a'..='z'
+            | '0'..='9'
+            | '-'
+            | '.'
+            | '_'
+            | '!'
+            | '$'
+            | '&'
+            | '\''
+            | '('
+            | ')'
+            | '*'
+            | '+'
+            | ','
+            | ';'
+            | '='
+            | ':'
+            | '@' => uri.push(c),
+            c => {
+                let mut buf = [0; 4];
+                for byte in c.encode_utf8(&mut buf).bytes() {
+                    write!(uri, "%{:02X}", byte).unwrap();
+                }
+            }
+        }
+    }
+    uri
+}
+
+/// The schema and key of a generic argument.
+#[derive(Clone)]
+struct GenericArg {
+    schema: Json,
+    key: String,
+    /// The generic variables of the definitions being built which this
+    /// argument is built from, along with their index in the definition
+    /// stack.
+    deps: HashSet<(usize, &'static str)>,
+}
+
+/// A definition whose schema is currently being built.
+struct StackDef {
+    hash: u64,
+    key: String,
+    /// The keys of the generic arguments of this definition by their
+    /// variable.
+    generic_arg_keys: HashMap<&'static str, String>,
+}
+
+struct SchemaCtx<'a> {
+    names: &'a DefNames,
+    defs_by_name:
+        HashMap<(Vec<&'static str>, &'static str), &'static TypeDefinition>,
+    generic_args: HashMap<&'static str, GenericArg>,
+    int64_repr: IntegerRepr,
+    int128_repr: IntegerRepr,
+    def_keys: HashSet<String>,
+    /// The definitions whose schemas are currently being built, used to
+    /// detect polymorphic recursion.
+    def_stack: Vec<StackDef>,
+    defs: Vec<(String, Json)>,
+}
+
+impl SchemaCtx<'_> {
+    fn lookup_name(
+        &self,
+        path: &[Ident],
+        name: &'static str,
+        generic_args: &[TypeExpr],
+    ) -> Option<&'static TypeDefinition> {
+        let type_def = *self
+            .defs_by_name
+            .get(&(path.iter().map(|Ident(part)| *part).collect(), name))?;
+        (type_def.generic_vars.len() == generic_args.len()).then_some(type_def)
+    }
+
+    fn schema(&mut self, expr: &TypeExpr) -> io::Result<Json> {
+        Ok(match expr {
+            TypeExpr::Ref(type_info) => self.ref_schema(type_info)?,
+            TypeExpr::LazyRef(type_info) => self.ref_schema(type_info())?,
+            TypeExpr::Name(TypeName {
+                path,
+                name: Ident(name),
+                generic_args,
+            }) => {
+                if path.is_empty() && generic_args.is_empty() {
+                    if let Some(GenericArg {
+                        schema,
+                        key: _,
+                        deps: _,
+                    }) = self.generic_args.get(name)
+                    {
+                        return Ok(schema.clone());
+                    }
+                }
+                if let Some(type_def) =
+                    self.lookup_name(path, name, generic_args)
+                {
+                    return self.def_schema(type_def, generic_args);
+                }
+                match (path.is_empty(), *name, *generic_args) {
+                    (true, "string" | "number" | "boolean" | "null", []) => {
+                        Json::type_(name)
+                    }
+                    (true, "bigint", []) => Json::type_("integer"),
+                    (true, "undefined" | "never", []) => {
+                        Json::object([("not", Json::Object(Vec::new()))])
+                    }
+                    (true, "Record", [_key, value]) => Json::object([
+                        ("type", Json::string("object")),
+                        ("additionalProperties", self.schema(value)?),
+                    ]),
+                    // types which can't be described allow any value
+                    _ => Json::Object(Vec::new()),
+                }
+            }
+            TypeExpr::String(TypeString { docs, value }) => {
+                Json::object([("const", Json::string(value))]).with_docs(docs)
+            }
+            TypeExpr::Tuple(TypeTuple { docs, elements }) => Json::object([
+                ("type", Json::string("array")),
+                (
+                    "prefixItems",
+                    Json::Array(
+                        elements
+                            .iter()
+                            .map(|expr| self.schema(expr))
+                            .collect::<io::Result<_>>()?,
+                    ),
+                ),
+                ("items", Json::Bool(false)),
+                ("minItems", Json::Number(elements.len().to_string())),
+            ])
+            .with_docs(docs),
+            TypeExpr::Object(object) => {
+                self.object_schema(object, object.deny_unknown_fields)?
+            }
+            TypeExpr::Array(TypeArray { docs, item }) => Json::object([
+                ("type", Json::string("array")),
+                ("items", self.schema(item)?),
+            ])
+            .with_docs(docs),
+            TypeExpr::Union(TypeUnion { docs, members }) => match members {
+                [] => Json::object([("not", Json::Object(Vec::new()))]),
+                [member] => self.schema(member)?,
+                _ => {
+                    // members with distinct tags match exclusively
+                    let keyword = if discriminator(members).is_some() {
+                        "oneOf"
+                    } else {
+                        "anyOf"
+                    };
+                    Json::object([(
+                        keyword,
+                        Json::Array(
+                            members
+                                .iter()
+                                .map(|expr| self.schema(expr))
+                                .collect::<io::Result<_>>()?,
+                        ),
+                    )])
+                }
+            }
+            .with_docs(docs),
+            TypeExpr::Intersection(TypeIntersection { docs, members }) => {
+                match (members, without_empty_object(members)) {
+                    ([member], _) | (_, Some(member)) => self.schema(member)?,
+                    _ => {
+                        // a closed object can't reject the properties of the
+                        // other members, so the whole intersection is closed
+                        // instead
+                        let mut closed = false;
+                        let schemas = members
+                            .iter()
+                            .map(|expr| match expr {
+                                TypeExpr::Object(object) => {
+                                    closed |= object.deny_unknown_fields;
+                                    self.object_schema(object, false)
+                                }
+                                _ => self.schema(expr),
+                            })
+                            .collect::<io::Result<_>>()?;
+                        let mut entries = vec![("allOf", Json::Array(schemas))];
+                        if closed {
+                            entries.push((
+                                "unevaluatedProperties",
+                                Json::Bool(false),
+                            ));
+                        }
+                        Json::object(entries)
+                    }
+                }
+                .with_docs(docs)
+            }
+            // raw types can't be described, so they allow any value
+            TypeExpr::Raw(_) => Json::Object(Vec::new()),
+        })
+    }
+
+    /// Returns the schema of an object type, which rejects properties other
+    /// than its fields if `closed` is set.
+    fn object_schema(
+        &mut self,
+        object: &TypeObject,
+        closed: bool,
+    ) -> io::Result<Json> {
+        let TypeObject {
+            docs,
+            index_signature,
+            fields,
+            deny_unknown_fields: _,
+        } = object;
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+        for ObjectField {
+            docs,
+            name:
+                TypeString {
+                    docs: _,
+                    value: name,
+                },
+            optional,
+            r#type,
+        } in *fields
+        {
+            properties
+                .push((name.to_string(), self.schema(r#type)?.with_docs(docs)));
+            if !optional {
+                required.push(Json::string(name));
+            }
+        }
+        let mut entries = vec![("type", Json::string("object"))];
+        if !properties.is_empty() {
+            entries.push(("properties", Json::Object(properties)));
+        }
+        if !required.is_empty() {
+            entries.push(("required", Json::Array(required)));
+        }
+        if let Some(IndexSignature {
+            docs: _,
+            name: _,
+            value,
+        }) = index_signature
+        {
+            entries.push(("additionalProperties", self.schema(value)?));
+        } else if closed {
+            entries.push(("additionalProperties", Json::Bool(false)));
+        }
+        Ok(Json::object(entries).with_docs(docs))
+    }
+
+    fn ref_schema(&mut self, type_info: &'static TypeInfo) -> io::Result<Json> {
+        match type_info {
+            TypeInfo::Native(NativeTypeInfo { r#ref }) => self.schema(r#ref),
+            TypeInfo::Defined(DefinedTypeInfo { def, generic_args }) => {
+                self.def_schema(def, generic_args)
+            }
+        }
+    }
+
+    /// Returns a reference to the definition of the given type with the
+    /// given generic arguments, adding it to the definitions if needed.
+    ///
+    /// Since JSON Schema has no generics, each instantiation of a generic type
+    /// is a separate definition. A generic type which refers to itself while
+    /// its schema is being built, with a generic argument built from and
+    /// larger than its own, would need infinitely many definitions, so it
+    /// results in an error.
+    fn def_schema(
+        &mut self,
+        type_def: &'static TypeDefinition,
+        generic_args: &[TypeExpr],
+    ) -> io::Result<Json> {
+        let TypeDefinition {
+            docs,
+            path: _,
+            name: _,
+            generic_vars,
+            def,
+            enum_values: _,
+            brand: _,
+            std_number: _,
+        } = type_def;
+        let generic_args = generic_args
+            .iter()
+            .map(|expr| {
+                let mut deps = HashSet::new();
+                self.generic_deps(expr, &mut deps);
+                Ok(GenericArg {
+                    schema: self.schema(expr)?,
+                    key: self.key(expr),
+                    deps,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let mut key = self.def_key(type_def);
+        if !generic_args.is_empty() {
+            key.push('<');
+            for (
+                i,
+                GenericArg {
+                    schema: _,
+                    key: arg_key,
+                    deps: _,
+                },
+            ) in generic_args.iter().enumerate()
+            {
+                if i > 0 {
+                    key.push_str(", ");
+                }
+                key.push_str(arg_key);
+            }
+            key.push('>');
+        }
+        if !self.def_keys.contains(&key) {
+            let hash = hash_type_definition(type_def);
+            // a generic argument which is built from, but larger than, the
+            // argument of an enclosing instantiation of the same definition
+            // keeps growing with each nested instantiation
+            let growing = self
+                .def_stack
+                .iter()
+                .enumerate()
+                .filter(|(_, outer)| outer.hash == hash)
+                .find(|(i, outer)| {
+                    generic_args.iter().any(|arg| {
+                        arg.deps.iter().any(|(dep_i, var)| {
+                            dep_i == i
+                                && matches!(
+                                    outer.generic_arg_keys.get(var),
+                                    Some(outer_key)
+                                        if arg.key.len() > outer_key.len()
+                                )
+                        })
+                    })
+                });
+            if let Some((_, outer)) = growing {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "type `{}` refers to itself as `{}`, which can't be \
+                         described without generics",
+                        outer.key, key
+                    ),
+                ));
+            }
+            self.def_keys.insert(key.clone());
+            let repr =
+                IntegerRepr::of(type_def, self.int64_repr, self.int128_repr);
+            let schema = match IntegerBounds::of(type_def) {
+                Some(bounds) if repr == Some(IntegerRepr::String) => {
+                    Json::object([
+                        ("type", Json::string("string")),
+                        ("pattern", Json::string(bounds.digits_pattern())),
+                    ])
+                }
+                Some(IntegerBounds {
+                    min,
+                    max,
+                    non_zero,
+                    bits: _,
+                }) => {
+                    let mut entries = vec![("type", Json::string("integer"))];
+                    if let Some(min) = min {
+                        entries.push(("minimum", Json::Number(min.to_owned())));
+                    }
+                    if let Some(max) = max {
+                        entries.push(("maximum", Json::Number(max.to_owned())));
+                    }
+                    if non_zero {
+                        entries.push((
+                            "not",
+                            Json::object([(
+                                "const",
+                                Json::Number("0".to_owned()),
+                            )]),
+                        ));
+                    }
+                    Json::object(entries)
+                }
+                None => {
+                    let generic_args = generic_vars
+                        .iter()
+                        .map(|Ident(generic_var)| *generic_var)
+                        .zip(generic_args)
+                        .collect::<HashMap<_, _>>();
+                    let generic_arg_keys = generic_args
+                        .iter()
+                        .map(|(var, arg)| (*var, arg.key.clone()))
+                        .collect();
+                    let outer_generic_args =
+                        mem::replace(&mut self.generic_args, generic_args);
+                    self.def_stack.push(StackDef {
+                        hash,
+                        key: key.clone(),
+                        generic_arg_keys,
+                    });
+                    let schema = self.schema(def);
+                    self.def_stack.pop();
+                    self.generic_args = outer_generic_args;
+                    schema?
+                }
+            };
+            self.defs.push((key.clone(), schema.with_docs(docs)));
+        }
+        Ok(Json::object([("$ref", Json::String(def_ref(&key)))]))
+    }
+
+    /// Collects the generic variables of the definitions being built which a
+    /// type expression is built from.
+    fn generic_deps(
+        &self,
+        expr: &TypeExpr,
+        deps: &mut HashSet<(usize, &'static str)>,
+    ) {
+        let visit =
+            |exprs: &[TypeExpr], deps: &mut HashSet<(usize, &'static str)>| {
+                for expr in exprs {
+                    self.generic_deps(expr, deps);
+                }
+            };
+        if let Some(type_info) = ref_type_info(expr) {
+            match type_info {
+                TypeInfo::Native(NativeTypeInfo { r#ref }) => {
+                    visit(std::slice::from_ref(r#ref), deps)
+                }
+                TypeInfo::Defined(DefinedTypeInfo {
+                    def: _,
+                    generic_args,
+                }) => visit(generic_args, deps),
+            }
+            return;
+        }
+        match expr {
+            TypeExpr::Ref(_) | TypeExpr::LazyRef(_) => {
+                unreachable!("references are handled above")
+            }
+            TypeExpr::Name(TypeName {
+                path,
+                name: Ident(name),
+                generic_args,
+            }) => {
+                if path.is_empty() && generic_args.is_empty() {
+                    if let Some(GenericArg {
+                        schema: _,
+                        key: _,
+                        deps: arg_deps,
+                    }) = self.generic_args.get(name)
+                    {
+                        deps.insert((self.def_stack.len() - 1, *name));
+                        deps.extend(arg_deps);
+                    }
+                }
+                visit(generic_args, deps);
+            }
+            TypeExpr::String(_) => {}
+            TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
+                visit(elements, deps)
+            }
+            TypeExpr::Object(TypeObject {
+                docs: _,
+                index_signature,
+                fields,
+                deny_unknown_fields: _,
+            }) => {
+                if let Some(IndexSignature {
+                    docs: _,
+                    name: _,
+                    value,
+                }) = index_signature
+                {
+                    visit(std::slice::from_ref(*value), deps);
+                }
+                for ObjectField {
+                    docs: _,
+                    name: _,
+                    optional: _,
+                    r#type,
+                } in *fields
+                {
+                    visit(std::slice::from_ref(r#type), deps);
+                }
+            }
+            TypeExpr::Array(TypeArray { docs: _, item }) => {
+                visit(std::slice::from_ref(*item), deps)
+            }
+            TypeExpr::Union(TypeUnion { docs: _, members })
+            | TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+                visit(members, deps)
+            }
+            // raw types are not part of the key
+            TypeExpr::Raw(_) => {}
+        }
+    }
+
+    fn def_key(&self, type_def: &'static TypeDefinition) -> String {
+        let mut key = String::new();
+        for Ident(path_part) in type_def.path {
+            key.push_str(path_part);
+            key.push('.');
+        }
+        key.push_str(self.names.get(type_def));
+        key
+    }
+
+    /// Returns a TypeScript-like representation of a type expression which
+    /// identifies it in the key of a generic instantiation.
+    fn key(&self, expr: &TypeExpr) -> String {
+        let join = |exprs: &[TypeExpr], sep: &str| {
+            exprs
+                .iter()
+                .map(|expr| self.key(expr))
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        let with_generics = |mut key: String, generic_args: &[TypeExpr]| {
+            if !generic_args.is_empty() {
+                key = format!("{}<{}>", key, join(generic_args, ", "));
+            }
+            key
+        };
+        if let Some(type_info) = ref_type_info(expr) {
+            return match type_info {
+                TypeInfo::Native(NativeTypeInfo { r#ref }) => self.key(r#ref),
+                TypeInfo::Defined(DefinedTypeInfo { def, generic_args }) => {
+                    with_generics(self.def_key(def), generic_args)
+                }
+            };
+        }
+        match expr {
+            TypeExpr::Ref(_) | TypeExpr::LazyRef(_) => {
+                unreachable!("references are handled above")
+            }
+            TypeExpr::Name(TypeName {
+                path,
+                name: Ident(name),
+                generic_args,
+            }) => {
+                if path.is_empty() && generic_args.is_empty() {
+                    if let Some(GenericArg {
+                        schema: _,
+                        key,
+                        deps: _,
+                    }) = self.generic_args.get(name)
+                    {
+                        return key.clone();
+                    }
+                }
+                if let Some(type_def) =
+                    self.lookup_name(path, name, generic_args)
+                {
+                    return with_generics(self.def_key(type_def), generic_args);
+                }
+                let mut key = String::new();
+                for Ident(path_part) in *path {
+                    key.push_str(path_part);
+                    key.push('.');
+                }
+                key.push_str(name);
+                with_generics(key, generic_args)
+            }
+            TypeExpr::String(TypeString { docs: _, value }) => {
+                JsString(value).to_string()
+            }
+            TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
+                format!("[{}]", join(elements, ", "))
+            }
+            TypeExpr::Object(TypeObject {
+                docs: _,
+                index_signature,
+                fields,
+                deny_unknown_fields: _,
+            }) => {
+                let mut key = "{".to_owned();
+                if let Some(IndexSignature {
+                    docs: _,
+                    name: Ident(name),
+                    value,
+                }) = index_signature
+                {
+                    write!(key, " [{}: string]: {};", name, self.key(value))
+                        .unwrap();
+                }
+                for ObjectField {
+                    docs: _,
+                    name:
+                        TypeString {
+                            docs: _,
+                            value: name,
+                        },
+                    optional,
+                    r#type,
+                } in *fields
+                {
+                    write!(
+                        key,
+                        " {}{}: {};",
+                        JsString(name),
+                        if *optional { "?" } else { "" },
+                        self.key(r#type)
+                    )
+                    .unwrap();
+                }
+                key.push_str(" }");
+                key
+            }
+            TypeExpr::Array(TypeArray { docs: _, item }) => {
+                format!("({})[]", self.key(item))
+            }
+            TypeExpr::Union(TypeUnion { docs: _, members }) => {
+                format!("({})", join(members, " | "))
+            }
+            TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+                format!("({})", join(members, " & "))
+            }
+            TypeExpr::Raw(TypeRaw { ts, deps: _ }) => format!("({})", ts),
+        }
+    }
+}
+
+/// Writes a [JSON Schema](https://json-schema.org/) (draft 2020-12) document
+/// describing `T` to the given writer.
+///
+/// The type definitions of `T` and all of its transitive dependencies are
+/// placed in the `$defs` of the document, keyed by their namespace path and
+/// name joined with dots (e.g. `x.y.Foo`), and referenced using `$ref`. Since
+/// JSON Schema has no generics, each instantiation of a generic type is a
+/// separate definition keyed by its generic arguments (e.g. `Foo<U8>`). A
+/// generic type which refers to itself with generic arguments that grow at
+/// each level of nesting (e.g. a field of type `Foo<Vec<T>>` in `Foo<T>`)
+/// would need infinitely many definitions, so it causes an error. Other
+/// arguments (e.g. a field of type `Foo<u8>` in `Foo<T>`) are fine.
+///
+/// Objects are described using `properties` and `required` (omitting
+/// optional fields), unions using `oneOf` if every member has a distinct tag
+/// (such as enums with `#[serde(tag = "...")]`) or `anyOf` otherwise, and
+/// doc comments using `description`.
+///
+/// # Example
+/// ```
+/// use serde::Serialize;
+/// use typescript_type_def::{write_json_schema, TypeDef};
+///
+/// /// A user.
+/// #[derive(Serialize, TypeDef)]
+/// struct User {
+///     name: String,
+///     #[serde(skip_serializing_if = "Option::is_none")]
+///     age: Option<u8>,
+/// }
+///
+/// let schema = {
+///     let mut buf = Vec::new();
+///     write_json_schema::<_, User>(&mut buf, Default::default()).unwrap();
+///     String::from_utf8(buf).unwrap()
+/// };
+/// assert_eq!(
+///     schema,
+///     r##"{
+///   "$schema": "https://json-schema.org/draft/2020-12/schema",
+///   "$ref": "#/$defs/User",
+///   "$defs": {
+///     "U8": {
+///       "type": "integer",
+///       "minimum": 0,
+///       "maximum": 255
+///     },
+///     "User": {
+///       "description": "A user.",
+///       "type": "object",
+///       "properties": {
+///         "name": {
+///           "type": "string"
+///         },
+///         "age": {
+///           "$ref": "#/$defs/U8"
+///         }
+///       },
+///       "required": [
+///         "name"
+///       ]
+///     }
+///   }
+/// }
+/// "##
+/// );
+/// ```
+pub fn write_json_schema<W, T>(
+    writer: W,
+    options: JsonSchemaOptions<'_>,
+) -> io::Result<Stats>
+where
+    W: io::Write,
+    T: TypeDef + ?Sized,
+{
+    write_json_schema_from_type_infos(writer, options, &[&T::INFO])
+}
+
+/// Writes a [JSON Schema](https://json-schema.org/) (draft 2020-12) document
+/// describing the given list of type info values to the given writer.
+///
+/// The type info values can be obtained using [`TypeDef::INFO`] on a type.
+///
+/// If more than one type info value is given, the document matches any of
+/// the types using `anyOf`. See [`write_json_schema`] for details on the
+/// produced schema.
+pub fn write_json_schema_from_type_infos<W>(
+    mut writer: W,
+    options: JsonSchemaOptions<'_>,
+    type_infos: &[&'static TypeInfo],
+) -> io::Result<Stats>
+where
+    W: io::Write,
+{
+    let JsonSchemaOptions {
+        id,
+        name_conflicts,
+        int64_repr,
+        int128_repr,
+    } = options;
+    let defs = IterDefDeps::new(type_infos).collect::<Vec<_>>();
+    let names =
+        DefNames::resolve(&defs, ModuleStyle::Namespaces, name_conflicts)?;
+    let mut ctx = SchemaCtx {
+        names: &names,
+        defs_by_name: HashMap::new(),
+        generic_args: HashMap::new(),
+        int64_repr,
+        int128_repr,
+        def_keys: HashSet::new(),
+        def_stack: Vec::new(),
+        defs: Vec::new(),
+    };
+    for &type_def in &defs {
+        ctx.defs_by_name
+            .entry((
+                type_def.path.iter().map(|Ident(part)| *part).collect(),
+                type_def.name.0,
+            ))
+            .or_insert(type_def);
+    }
+
+    let mut entries =
+        vec![("$schema".to_owned(), Json::string(SCHEMA_DIALECT))];
+    if let Some(id) = id {
+        entries.push(("$id".to_owned(), Json::string(id)));
+    }
+    let root = match type_infos {
+        [type_info] => ctx.ref_schema(type_info)?,
+        _ => Json::object([(
+            "anyOf",
+            Json::Array(
+                type_infos
+                    .iter()
+                    .map(|type_info| ctx.ref_schema(type_info))
+                    .collect::<io::Result<_>>()?,
+            ),
+        )]),
+    };
+    if let Json::Object(root_entries) = root {
+        entries.extend(root_entries);
+    }
+    let stats = Stats {
+        type_definitions: ctx.defs.len(),
+    };
+    if !ctx.defs.is_empty() {
+        entries.push(("$defs".to_owned(), Json::Object(ctx.defs)));
+    }
+    Json::Object(entries).write(&mut writer, 0)?;
+    writeln!(writer)?;
+    Ok(stats)
+}
@@ -25,9 +25,52 @@
 //! [`TypeInfo::write_ref_expr`](type_expr::TypeInfo::write_ref_expr) that can
 //! be used for this purpose.
 //!
+//! To check at runtime that untrusted data (such as JSON received in a browser)
+//! matches these type definitions, use [`write_type_guards`] to generate a
+//! TypeScript module of type guard functions for the same types.
+//!
+//! If you use [Zod](https://zod.dev/), [`write_zod_schemas`] generates Zod
+//! schemas for your types instead.
+//!
+//! The same type info can also be used to write a
+//! [JSON Schema](https://json-schema.org/) document using [`write_json_schema`],
+//! for example to validate requests in an API gateway.
+//!
+//! The JSON a type accepts when deserializing can differ from the JSON it
+//! produces, for example a field with `#[serde(default)]` may be omitted from
+//! a request body but is always present in a response. Such types have a
+//! separate input type definition described by [`TypeDef::INFO_DE`], which
+//! [`write_definition_file_with_inputs`] emits alongside the output type
+//! definitions (e.g. as `FooInput` next to `Foo`).
+//!
 //! # Features
 //!
 //! * `json_value` - Adds [`TypeDef`] impls for JSON value types from `serde_json`.
+//! * `chrono` - Adds [`TypeDef`] impls for date and time types from `chrono`.
+//! * `time` - Adds [`TypeDef`] impls for date and time types from `time`. The
+//!   date and time types are emitted as strings, which assumes that the
+//!   `serde-human-readable` feature of `time` is enabled (without it they are
+//!   serialized as tuples).
+//! * `uuid` - Adds a [`TypeDef`] impl for `Uuid` from `uuid`.
+//! * `ulid` - Adds a [`TypeDef`] impl for `Ulid` from `ulid`.
+//! * `bson` - Adds a [`TypeDef`] impl for `ObjectId` from `bson`.
+//! * `rust_decimal` - Adds a [`TypeDef`] impl for `Decimal` from `rust_decimal`,
+//!   which is serialized as a string.
+//! * `rust_decimal_float` - Emits `Decimal` as a number instead, which assumes
+//!   that the `serde-float` feature of `rust_decimal` is enabled (without it
+//!   `Decimal` is serialized as a string).
+//! * `bigdecimal` - Adds a [`TypeDef`] impl for `BigDecimal` from `bigdecimal`.
+//! * `num-bigint` - Adds [`TypeDef`] impls for `BigInt` and `BigUint` from
+//!   `num-bigint`.
+//! * `ordered-float` - Adds [`TypeDef`] impls for `OrderedFloat` and `NotNan` from
+//!   `ordered-float`.
+//! * `indexmap` - Adds [`TypeDef`] impls for `IndexMap` and `IndexSet` from
+//!   `indexmap`.
+//! * `smallvec` - Adds a [`TypeDef`] impl for `SmallVec` from `smallvec`.
+//! * `arrayvec` - Adds [`TypeDef`] impls for `ArrayVec` and `ArrayString` from
+//!   `arrayvec`.
+//! * `bytes` - Adds [`TypeDef`] impls for `Bytes` and `BytesMut` from `bytes`.
+//! * `heapless` - Adds [`TypeDef`] impls for `Vec` and `String` from `heapless`.
 //!
 //! # Examples
 //!
@@ -213,11 +256,32 @@
 mod emit;
 mod impls;
 mod iter_def_deps;
+mod json_schema;
+mod modules;
 pub mod type_expr;
+mod type_guards;
+mod zod;
 
-pub use crate::emit::{
-    write_definition_file, write_definition_file_from_type_infos,
-    DefinitionFileOptions, Stats, TypeDef,
+pub use crate::{
+    emit::{
+        write_definition_file, write_definition_file_from_type_infos,
+        write_definition_file_from_type_infos_with_inputs,
+        write_definition_file_with_inputs, DefinitionFileOptions, IntegerRepr,
+        ModuleStyle, NameConflictError, NameConflicts, Stats, TypeDef,
+    },
+    json_schema::{
+        write_json_schema, write_json_schema_from_type_infos, JsonSchemaOptions,
+    },
+    modules::{
+        write_definition_modules, write_definition_modules_from_type_infos,
+        DefinitionModulesOptions, ModuleSink,
+    },
+    type_guards::{
+        write_type_guards, write_type_guards_from_type_infos, TypeGuardOptions,
+    },
+    zod::{
+        write_zod_schemas, write_zod_schemas_from_type_infos, ZodSchemaOptions,
+    },
 };
 
 /// A derive proc-macro for the [`TypeDef`] trait.
@@ -229,7 +293,8 @@ pub use crate::emit::{
 /// and will generate a [`TypeDef`] implementation which matches the shape
 /// of the JSON produced by using [`serde_json`](https://docs.rs/serde_json/) on
 /// the target type. This macro will also read and adapt to `#[serde(...)]`
-/// attributes on the target type's definition.
+/// attributes on the target type's definition. Recursive and mutually
+/// recursive types are supported.
 ///
 /// This macro also reads the following attributes:
 /// * `#[type_def(namespace = "x.y.z")]` on the struct/enum body puts the
@@ -242,6 +307,47 @@ pub use crate::emit::{
 ///   JSON format matches the JSON format of the field's type. This
 ///   attribute can be used to specify the type definition for a foreign
 ///   type using your own type.
+/// * `#[type_def(remote = "other_crate::Type")]` (or
+///   `#[serde(remote = "...")]`) on the struct/enum body of a local mirror of
+///   a foreign type names its type definition after the foreign type, like
+///   serde's [remote derive](https://serde.rs/remote-derive.html). Fields of
+///   the foreign type can then use the definition with
+///   `#[type_def(with = "MirrorType")]`, which works like `type_of` and is
+///   usually paired with `#[serde(with = "MirrorType")]`.
+/// * `#[type_def(ts = "Record<string, unknown>")]` on a struct or tuple
+///   field, or on the struct/enum body, uses the given raw TypeScript type
+///   expression as the type of the field or as the type definition (see
+///   [`TypeRaw`](type_expr::TypeRaw)). Type definitions referenced by the
+///   expression must use their emitted names (e.g. `types.Foo`) and must be
+///   listed as Rust types with `#[type_def(ts_deps = "Foo, Bar<u8>")]` so
+///   that they are emitted too.
+/// * `#[type_def(int_repr = "string")]` on an integer struct or tuple field
+///   emits the field as `string` instead of the integer type's alias, for
+///   fields which are serialized as strings (e.g. using
+///   [`serde_with::DisplayFromStr`](https://docs.rs/serde_with/latest/serde_with/struct.DisplayFromStr.html)).
+///   The representation can also be `"bigint"` or `"number"`, and an
+///   `Option` field becomes <code>string \| null</code>. To change the
+///   representation of all 64-bit or 128-bit integers, use the
+///   [`int64_repr`](crate::DefinitionFileOptions::int64_repr) and
+///   [`int128_repr`](crate::DefinitionFileOptions::int128_repr) options of
+///   [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+/// * `#[type_def(const_object)]` on an enum with only unit variants also
+///   emits a `const` object with the same name as the type, mapping each
+///   variant name to its serialized value (e.g. `export const Color = {
+///   Red: "Red" } as const;`), and a `ColorValues` array of all values.
+///   `#[type_def(ts_enum)]` instead emits a TypeScript `enum` in place of
+///   the type definition, along with the same values array. Since these are
+///   runtime values, the definition file must be a `.ts` module rather than
+///   a `.d.ts` declaration file.
+/// * `#[type_def(brand)]` on a newtype struct (or a `#[serde(transparent)]`
+///   struct) emits it as a branded type which is incompatible with other
+///   types of the same shape (e.g. `export type UserId = (types.U64 & {
+///   readonly __brand: "UserId" });`), along with a function of the same
+///   name which casts a value of the inner type to it (e.g. `UserId(123)`).
+///   This also requires a `.ts` module. To brand the aliases of `std` numeric
+///   types, use the
+///   [`brand_numbers`](crate::DefinitionFileOptions::brand_numbers) option
+///   of [`DefinitionFileOptions`](crate::DefinitionFileOptions).
 ///
 /// ## `serde` attribute support
 ///
@@ -254,49 +360,55 @@ pub use crate::emit::{
 /// | Attribute | Support |
 /// |:-|:-:|
 /// | [`#[serde(rename = "name")]`](https://serde.rs/container-attrs.html#rename) | ✓ |
+/// | [`#[serde(rename(serialize = "ser_name", deserialize = "de_name"))]`](https://serde.rs/container-attrs.html#rename) | ✓ |
 /// | [`#[serde(rename_all = "...")]`](https://serde.rs/container-attrs.html#rename_all) | ✓ |
-/// | [`#[serde(deny_unknown_fields)]`](https://serde.rs/container-attrs.html#deny_unknown_fields) | ? |
+/// | [`#[serde(rename_all(serialize = "...", deserialize = "..."))]`](https://serde.rs/container-attrs.html#rename_all) | ✓ |
+/// | [`#[serde(rename_all_fields = "...")]`](https://serde.rs/container-attrs.html#rename_all_fields) | ✓ |
+/// | [`#[serde(deny_unknown_fields)]`](https://serde.rs/container-attrs.html#deny_unknown_fields) | ✓ |
 /// | [`#[serde(tag = "type")]`](https://serde.rs/container-attrs.html#tag) | ✓ |
 /// | [`#[serde(tag = "t", content = "c")]`](https://serde.rs/container-attrs.html#tag--content) | ✓ |
 /// | [`#[serde(untagged)]`](https://serde.rs/container-attrs.html#untagged) | ✓ |
 /// | [`#[serde(bound = "T: MyTrait")]`](https://serde.rs/container-attrs.html#bound) | ? |
-/// | [`#[serde(default)]`](https://serde.rs/container-attrs.html#default) | ? |
-/// | [`#[serde(default = "path")]`](https://serde.rs/container-attrs.html#default--path) | ? |
-/// | [`#[serde(remote = "...")]`](https://serde.rs/container-attrs.html#remote) | ✗ |
+/// | [`#[serde(default)]`](https://serde.rs/container-attrs.html#default) | ✓ |
+/// | [`#[serde(default = "path")]`](https://serde.rs/container-attrs.html#default--path) | ✓ |
+/// | [`#[serde(remote = "...")]`](https://serde.rs/container-attrs.html#remote) | ✓ |
 /// | [`#[serde(transparent)]`](https://serde.rs/container-attrs.html#transparent) | ✓ |
-/// | [`#[serde(from = "FromType")]`](https://serde.rs/container-attrs.html#from) | ✗ |
-/// | [`#[serde(try_from = "FromType")]`](https://serde.rs/container-attrs.html#try_from) | ✗ |
-/// | [`#[serde(into = "IntoType")]`](https://serde.rs/container-attrs.html#into) | ✗ |
+/// | [`#[serde(from = "FromType")]`](https://serde.rs/container-attrs.html#from) | ✓ |
+/// | [`#[serde(try_from = "FromType")]`](https://serde.rs/container-attrs.html#try_from) | ✓ |
+/// | [`#[serde(into = "IntoType")]`](https://serde.rs/container-attrs.html#into) | ✓ |
 /// | [`#[serde(crate = "...")]`](https://serde.rs/container-attrs.html#crate) | ✗ |
 ///
 /// ### Variant Attributes
 /// | Attribute | Support |
 /// |:-|:-:|
 /// | [`#[serde(rename = "name")]`](https://serde.rs/variant-attrs.html#rename) | ✓ |
-/// | [`#[serde(alias = "name")]`](https://serde.rs/variant-attrs.html#alias) | ? |
+/// | [`#[serde(rename(serialize = "ser_name", deserialize = "de_name"))]`](https://serde.rs/variant-attrs.html#rename) | ✓ |
+/// | [`#[serde(alias = "name")]`](https://serde.rs/variant-attrs.html#alias) | ✓ |
 /// | [`#[serde(rename_all = "...")]`](https://serde.rs/variant-attrs.html#rename_all) | ✓ |
+/// | [`#[serde(rename_all(serialize = "...", deserialize = "..."))]`](https://serde.rs/variant-attrs.html#rename_all) | ✓ |
 /// | [`#[serde(skip)]`](https://serde.rs/variant-attrs.html#skip) | ✓ |
-/// | [`#[serde(skip_serializing)]`](https://serde.rs/variant-attrs.html#skip_serializing) | ✗ |
-/// | [`#[serde(skip_deserializing)]`](https://serde.rs/variant-attrs.html#skip_deserializing) | ✗ |
+/// | [`#[serde(skip_serializing)]`](https://serde.rs/variant-attrs.html#skip_serializing) | ✓ |
+/// | [`#[serde(skip_deserializing)]`](https://serde.rs/variant-attrs.html#skip_deserializing) | ✓ |
 /// | [`#[serde(serialize_with = "path")]`](https://serde.rs/variant-attrs.html#serialize_with) | ✗ |
 /// | [`#[serde(deserialize_with = "path")]`](https://serde.rs/variant-attrs.html#deserialize_with) | ✗ |
 /// | [`#[serde(with = "module")]`](https://serde.rs/variant-attrs.html#with) | ✗ |
 /// | [`#[serde(bound = "T: MyTrait")]`](https://serde.rs/variant-attrs.html#bound) | ? |
 /// | [`#[serde(borrow)]`](https://serde.rs/variant-attrs.html#borrow) | ? |
 /// | [`#[serde(borrow = "'a + 'b + ...")]`](https://serde.rs/variant-attrs.html#borrow) | ? |
-/// | [`#[serde(other)]`](https://serde.rs/variant-attrs.html#other) | ? |
+/// | [`#[serde(other)]`](https://serde.rs/variant-attrs.html#other) | ✓ |
 ///
 /// ### Field Attributes
 /// | Attribute | Support |
 /// |:-|:-:|
 /// | [`#[serde(rename = "name")]`](https://serde.rs/field-attrs.html#rename) | ✓ |
-/// | [`#[serde(alias = "name")]`](https://serde.rs/field-attrs.html#alias) | ? |
+/// | [`#[serde(rename(serialize = "ser_name", deserialize = "de_name"))]`](https://serde.rs/field-attrs.html#rename) | ✓ |
+/// | [`#[serde(alias = "name")]`](https://serde.rs/field-attrs.html#alias) | ✓ |
 /// | [`#[serde(default)]`](https://serde.rs/field-attrs.html#default) | ✓ |
 /// | [`#[serde(default = "path")]`](https://serde.rs/field-attrs.html#default--path) | ✓ |
 /// | [`#[serde(flatten)]`](https://serde.rs/field-attrs.html#flatten) | ✓ |
 /// | [`#[serde(skip)]`](https://serde.rs/field-attrs.html#skip) | ✓ |
-/// | [`#[serde(skip_serializing)]`](https://serde.rs/field-attrs.html#skip_serializing) | ✗ |
-/// | [`#[serde(skip_deserializing)]`](https://serde.rs/field-attrs.html#skip_deserializing) | ✗ |
+/// | [`#[serde(skip_serializing)]`](https://serde.rs/field-attrs.html#skip_serializing) | ✓ |
+/// | [`#[serde(skip_deserializing)]`](https://serde.rs/field-attrs.html#skip_deserializing) | ✓ |
 /// | [`#[serde(skip_serializing_if = "path")]`](https://serde.rs/field-attrs.html#skip_serializing_if) | ✓ |
 /// | [`#[serde(serialize_with = "path")]`](https://serde.rs/field-attrs.html#serialize_with) | ✗ |
 /// | [`#[serde(deserialize_with = "path")]`](https://serde.rs/field-attrs.html#deserialize_with) | ✗ |
@@ -0,0 +1,438 @@
+use crate::{
+    emit::{
+        DefNames, EmitCtx, IntegerRepr, JsString, ModuleStyle, NameConflicts,
+        Stats, TypeDef,
+    },
+    iter_def_deps::IterDefDeps,
+    type_expr::{Ident, TypeDefinition, TypeInfo},
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A destination for the TypeScript modules produced by
+/// [`write_definition_modules`].
+///
+/// This trait is implemented for [`&Path`](std::path::Path) and
+/// [`PathBuf`](std::path::PathBuf), which write each module as a file in that
+/// directory (creating any missing directories), and for
+/// [`BTreeMap<String, Vec<u8>>`](std::collections::BTreeMap), which collects
+/// the module contents in memory keyed by their path.
+pub trait ModuleSink {
+    /// Creates a new module at the given path and returns a writer for its
+    /// contents.
+    ///
+    /// The path is relative and uses `/` as a separator, e.g. `x/y.ts`.
+    fn create_module(
+        &mut self,
+        path: &str,
+    ) -> io::Result<Box<dyn io::Write + '_>>;
+}
+
+impl<S> ModuleSink for &mut S
+where
+    S: ModuleSink + ?Sized,
+{
+    fn create_module(
+        &mut self,
+        path: &str,
+    ) -> io::Result<Box<dyn io::Write + '_>> {
+        S::create_module(self, path)
+    }
+}
+
+impl ModuleSink for &Path {
+    fn create_module(
+        &mut self,
+        path: &str,
+    ) -> io::Result<Box<dyn io::Write + '_>> {
+        create_module_file(self, path)
+    }
+}
+
+impl ModuleSink for PathBuf {
+    fn create_module(
+        &mut self,
+        path: &str,
+    ) -> io::Result<Box<dyn io::Write + '_>> {
+        create_module_file(self, path)
+    }
+}
+
+fn create_module_file(
+    dir: &Path,
+    path: &str,
+) -> io::Result<Box<dyn io::Write>> {
+    let path = dir.join(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(Box::new(io::BufWriter::new(fs::File::create(path)?)))
+}
+
+impl ModuleSink for BTreeMap<String, Vec<u8>> {
+    fn create_module(
+        &mut self,
+        path: &str,
+    ) -> io::Result<Box<dyn io::Write + '_>> {
+        let buf = self.entry(path.to_owned()).or_default();
+        buf.clear();
+        Ok(Box::new(buf))
+    }
+}
+
+/// Options for customizing the output of [`write_definition_modules`].
+///
+/// The default options are:
+/// ```
+/// # use typescript_type_def::{
+/// #     DefinitionModulesOptions, IntegerRepr, NameConflicts,
+/// # };
+/// # let default =
+/// DefinitionModulesOptions {
+///     header: Some("// AUTO-GENERATED by typescript-type-def\n"),
+///     root_module: "index",
+///     name_conflicts: NameConflicts::Error,
+///     int64_repr: IntegerRepr::Number,
+///     int128_repr: IntegerRepr::String,
+///     brand_numbers: false,
+/// }
+/// # ;
+/// # assert_eq!(default, Default::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DefinitionModulesOptions<'a> {
+    /// Text to be emitted at the start of each module.
+    ///
+    /// If `Some`, the string should contain the exact content of the header as
+    /// TypeScript code (usually in the form of comments). If `None`, no header
+    /// will be added.
+    pub header: Option<&'a str>,
+    /// The name of the module containing type definitions which have no
+    /// namespace.
+    ///
+    /// A namespace with the same name would be written to the same module, so
+    /// it causes an error.
+    pub root_module: &'a str,
+    /// How to handle distinct type definitions which have the same name and
+    /// namespace.
+    pub name_conflicts: NameConflicts,
+    /// How the aliases of 64-bit integer types are emitted.
+    ///
+    /// See the [`int64_repr`](crate::DefinitionFileOptions::int64_repr)
+    /// option of [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+    pub int64_repr: IntegerRepr,
+    /// How the aliases of 128-bit integer types are emitted.
+    ///
+    /// See the [`int128_repr`](crate::DefinitionFileOptions::int128_repr)
+    /// option of [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+    pub int128_repr: IntegerRepr,
+    /// Whether the aliases of `std` numeric types are emitted as branded
+    /// types.
+    ///
+    /// See the [`brand_numbers`](crate::DefinitionFileOptions::brand_numbers)
+    /// option of [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+    pub brand_numbers: bool,
+}
+
+impl Default for DefinitionModulesOptions<'_> {
+    fn default() -> Self {
+        Self {
+            header: Some("// AUTO-GENERATED by typescript-type-def\n"),
+            root_module: "index",
+            name_conflicts: NameConflicts::Error,
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+            brand_numbers: false,
+        }
+    }
+}
+
+/// The imports of a single module, along with the names they are available as
+/// in that module.
+pub(crate) struct ModuleImports {
+    module: Vec<&'static str>,
+    used_names: HashSet<String>,
+    imports: BTreeMap<Vec<&'static str>, BTreeMap<String, Import>>,
+}
+
+/// A definition imported from another module.
+struct Import {
+    /// The name the definition is available as.
+    local_name: String,
+    /// Whether the definition has a runtime value (such as a brand constructor
+    /// or a `const` object), which a type-only import doesn't include.
+    value: bool,
+}
+
+impl ModuleImports {
+    fn new(
+        module: Vec<&'static str>,
+        defs: &[&'static TypeDefinition],
+        names: &DefNames,
+    ) -> Self {
+        Self {
+            module,
+            used_names: defs
+                .iter()
+                .map(|def| names.get(def).to_owned())
+                .collect(),
+            imports: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the name that the given definition can be referred to by in
+    /// this module, importing it if necessary.
+    ///
+    /// If `value` is set, the definition has a runtime value which is
+    /// imported along with its type.
+    pub(crate) fn local_name(
+        &mut self,
+        path: &[Ident],
+        name: &str,
+        value: bool,
+    ) -> String {
+        let module = path.iter().map(|Ident(part)| *part).collect::<Vec<_>>();
+        if module == self.module {
+            return name.to_owned();
+        }
+        let Self {
+            used_names,
+            imports,
+            ..
+        } = self;
+        let import = imports
+            .entry(module)
+            .or_default()
+            .entry(name.to_owned())
+            .or_insert_with(|| {
+                let mut local_name = name.to_owned();
+                if used_names.contains(&local_name) {
+                    // use the namespace path to disambiguate
+                    local_name = path
+                        .iter()
+                        .map(|Ident(part)| *part)
+                        .chain([name])
+                        .collect::<Vec<_>>()
+                        .join("_");
+                }
+                used_names.insert(local_name.clone());
+                Import {
+                    local_name,
+                    value: false,
+                }
+            });
+        import.value |= value;
+        import.local_name.clone()
+    }
+}
+
+/// Returns the file path segments of the module for a namespace path.
+fn module_segments<'a>(
+    module: &[&'a str],
+    options: &DefinitionModulesOptions<'a>,
+) -> Vec<&'a str> {
+    if module.is_empty() {
+        vec![options.root_module]
+    } else {
+        module.to_vec()
+    }
+}
+
+/// Writes an import statement of the given definitions if there are any.
+fn write_import<'a>(
+    w: &mut dyn io::Write,
+    keyword: &str,
+    imports: impl IntoIterator<Item = (&'a String, &'a Import)>,
+    specifier: &str,
+) -> io::Result<()> {
+    let mut imports = imports.into_iter().peekable();
+    if imports.peek().is_none() {
+        return Ok(());
+    }
+    write!(w, "{} {{ ", keyword)?;
+    let mut first = true;
+    for (name, Import { local_name, .. }) in imports {
+        if !first {
+            write!(w, ", ")?;
+        }
+        write!(w, "{}", name)?;
+        if name != local_name {
+            write!(w, " as {}", local_name)?;
+        }
+        first = false;
+    }
+    writeln!(w, " }} from {};", JsString(specifier))
+}
+
+/// Returns the relative import specifier of module `to` from module `from`.
+fn relative_import(from: &[&str], to: &[&str]) -> String {
+    let from_dir = &from[..from.len() - 1];
+    let common = from_dir
+        .iter()
+        .zip(to)
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(to.len() - 1);
+    let mut specifier = if common == from_dir.len() {
+        "./".to_owned()
+    } else {
+        "../".repeat(from_dir.len() - common)
+    };
+    specifier.push_str(&to[common..].join("/"));
+    specifier
+}
+
+/// Writes TypeScript modules containing type definitions for `T` to the given
+/// sink, with one module per namespace.
+///
+/// Each type definition is placed in a module according to its namespace
+/// path from `#[type_def(namespace = "x.y.z")]`. For example, a type in
+/// namespace `x.y` is placed in the module `x/y.ts`. Types without a namespace
+/// are placed in the [`root_module`](DefinitionModulesOptions::root_module).
+/// Each module exports its type definitions at the top level, and references
+/// to types defined in other modules are imported using relative
+/// `import type` statements. Definitions which also have a runtime value
+/// (branded types and enums with `#[type_def(const_object)]` or
+/// `#[type_def(ts_enum)]`) are imported using `import` statements instead.
+///
+/// Each module will also include a header comment indicating that it was
+/// auto-generated by this library. This is configurable with the
+/// [`header`](DefinitionModulesOptions::header) option.
+///
+/// # Example
+/// ```
+/// use serde::Serialize;
+/// use std::collections::BTreeMap;
+/// use typescript_type_def::{write_definition_modules, TypeDef};
+///
+/// #[derive(Serialize, TypeDef)]
+/// #[type_def(namespace = "models")]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[derive(Serialize, TypeDef)]
+/// struct Response {
+///     user: User,
+/// }
+///
+/// let mut modules = BTreeMap::new();
+/// write_definition_modules::<_, Response>(&mut modules, Default::default())
+///     .unwrap();
+/// assert_eq!(
+///     String::from_utf8(modules.remove("index.ts").unwrap()).unwrap(),
+///     r#"// AUTO-GENERATED by typescript-type-def
+///
+/// import type { User } from "./models";
+///
+/// export type Response = {
+///     "user": User;
+/// };
+/// "#
+/// );
+/// assert_eq!(
+///     String::from_utf8(modules.remove("models.ts").unwrap()).unwrap(),
+///     r#"// AUTO-GENERATED by typescript-type-def
+///
+/// export type User = {
+///     "name": string;
+/// };
+/// "#
+/// );
+/// ```
+pub fn write_definition_modules<S, T>(
+    sink: S,
+    options: DefinitionModulesOptions<'_>,
+) -> io::Result<Stats>
+where
+    S: ModuleSink,
+    T: TypeDef + ?Sized,
+{
+    write_definition_modules_from_type_infos(sink, options, &[&T::INFO])
+}
+
+/// Writes TypeScript modules containing type definitions for the given list of
+/// type info values to the given sink, with one module per namespace.
+///
+/// The type info values can be obtained using [`TypeDef::INFO`] on a type.
+///
+/// See [`write_definition_modules`] for details on how the modules are laid
+/// out.
+pub fn write_definition_modules_from_type_infos<S>(
+    mut sink: S,
+    options: DefinitionModulesOptions<'_>,
+    type_infos: &[&'static TypeInfo],
+) -> io::Result<Stats>
+where
+    S: ModuleSink,
+{
+    let defs = IterDefDeps::new(type_infos).collect::<Vec<_>>();
+    let names = DefNames::resolve(
+        &defs,
+        ModuleStyle::Namespaces,
+        options.name_conflicts,
+    )?;
+    let mut modules = BTreeMap::<_, Vec<_>>::new();
+    for def in defs {
+        let module =
+            def.path.iter().map(|Ident(part)| *part).collect::<Vec<_>>();
+        modules.entry(module).or_default().push(def);
+    }
+    if modules.contains_key(&Vec::new())
+        && modules.contains_key(&vec![options.root_module])
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "namespace `{}` would be written to the same module as the \
+                root module, choose a different `root_module`",
+                options.root_module
+            ),
+        ));
+    }
+
+    let mut stats = Stats {
+        type_definitions: 0,
+    };
+    for (module, defs) in modules {
+        let segments = module_segments(&module, &options);
+        let mut imports = ModuleImports::new(module, &defs, &names);
+        let mut body = Vec::new();
+        let mut ctx = EmitCtx::new_module(&mut body, &mut imports, &names)
+            .with_integer_reprs(options.int64_repr, options.int128_repr)
+            .with_brand_numbers(options.brand_numbers);
+        ctx.emit_type_def(defs)?;
+        stats.type_definitions += ctx.stats.type_definitions;
+
+        let mut w =
+            sink.create_module(&format!("{}.ts", segments.join("/")))?;
+        if let Some(header) = options.header {
+            writeln!(w, "{}", header)?;
+        }
+        for (module, names) in &imports.imports {
+            let specifier =
+                relative_import(&segments, &module_segments(module, &options));
+            write_import(
+                &mut w,
+                "import type",
+                names.iter().filter(|(_, import)| !import.value),
+                &specifier,
+            )?;
+            write_import(
+                &mut w,
+                "import",
+                names.iter().filter(|(_, import)| import.value),
+                &specifier,
+            )?;
+        }
+        if !imports.imports.is_empty() {
+            writeln!(w)?;
+        }
+        w.write_all(&body)?;
+        w.flush()?;
+    }
+    Ok(stats)
+}
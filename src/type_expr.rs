@@ -66,6 +66,64 @@ pub struct TypeDefinition {
     pub generic_vars: List<Ident>,
     /// The definition of this type.
     pub def: TypeExpr,
+    /// The runtime values of this type, which are emitted alongside its type
+    /// definition.
+    ///
+    /// This is set by [`#[derive(TypeDef)]`](macro@crate::TypeDef) for
+    /// fieldless enums with `#[type_def(ts_enum)]` or
+    /// `#[type_def(const_object)]`.
+    pub enum_values: Option<EnumValues>,
+    /// Whether this type is branded, making it incompatible with other types
+    /// with the same definition.
+    ///
+    /// A branded type is emitted as an intersection with a unique brand (e.g.
+    /// `number & { readonly __brand: "UserId" }`), along with a function of the
+    /// same name which casts a value of the underlying type to it. This is set
+    /// by [`#[derive(TypeDef)]`](macro@crate::TypeDef) for newtypes with
+    /// `#[type_def(brand)]`.
+    pub brand: bool,
+    /// Whether this type is the alias of a `std` numeric type (e.g. `U8` for
+    /// `u8`).
+    ///
+    /// This is only set by the [`TypeDef`](crate::TypeDef) impls of those
+    /// types. Emitters use it to apply options such as
+    /// [`int64_repr`](crate::DefinitionFileOptions::int64_repr) and to check
+    /// the range of integers, so other definitions with the same name aren't
+    /// mistaken for them.
+    pub std_number: bool,
+}
+
+/// The runtime values of a fieldless enum.
+#[derive(Debug, Clone, Copy)]
+pub struct EnumValues {
+    /// How the values are emitted.
+    pub style: EnumStyle,
+    /// The members of the enum.
+    pub members: List<EnumMember>,
+}
+
+/// How the runtime values of a fieldless enum are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnumStyle {
+    /// A TypeScript `enum`, which replaces the type definition.
+    ///
+    /// For example `export enum Color { Red = "Red" }`.
+    Enum,
+    /// A `const` object with the same name as the type definition.
+    ///
+    /// For example `export const Color = { Red: "Red" } as const;`.
+    ConstObject,
+}
+
+/// A member of a fieldless enum.
+#[derive(Debug, Clone, Copy)]
+pub struct EnumMember {
+    /// The documentation for this member.
+    pub docs: Option<Docs>,
+    /// The name of this member.
+    pub name: Ident,
+    /// The serialized value of this member.
+    pub value: &'static str,
 }
 
 /// A TypeScript type expression.
@@ -77,6 +135,14 @@ pub struct TypeDefinition {
 pub enum TypeExpr {
     /// A reference to another type.
     Ref(&'static TypeInfo),
+    /// A lazily-resolved reference to another type.
+    ///
+    /// This behaves the same as [`TypeExpr::Ref`], except that the referenced
+    /// type info is only obtained by calling the function when it is needed.
+    /// This allows recursive and mutually recursive types to refer to each
+    /// other's [`INFO`](crate::emit::TypeDef::INFO) without creating a cycle
+    /// in constant evaluation.
+    LazyRef(fn() -> &'static TypeInfo),
     /// A reference to a bare type name which should already be defined.
     Name(TypeName),
     /// A type-level string literal.
@@ -91,6 +157,8 @@ pub enum TypeExpr {
     Union(TypeUnion),
     /// An intersection type.
     Intersection(TypeIntersection),
+    /// A raw TypeScript type expression.
+    Raw(TypeRaw),
 }
 
 /// A reference to a built-in TypeScript type, analogous to a Rust path with
@@ -141,6 +209,12 @@ pub struct TypeObject {
     pub index_signature: Option<IndexSignature>,
     /// The fields of this object.
     pub fields: List<ObjectField>,
+    /// Whether properties other than the fields of this object are rejected.
+    ///
+    /// TypeScript object types cannot express this, but other emitters use it
+    /// to produce closed object schemas. It is set by the derive macro for
+    /// types with `#[serde(deny_unknown_fields)]`.
+    pub deny_unknown_fields: bool,
 }
 
 /// An index signature of a TypeScript object type.
@@ -217,6 +291,20 @@ pub struct TypeIntersection {
     pub members: List<TypeExpr>,
 }
 
+/// A raw TypeScript type expression, which is emitted verbatim.
+///
+/// Since the expression is opaque to this library, any type definitions it
+/// references must be listed in `deps` so that they are emitted too, and it
+/// must refer to them by the names they are emitted with (e.g. `types.Foo`).
+/// Raw types are not checked by type guards, Zod schemas or JSON Schemas.
+#[derive(Debug, Clone, Copy)]
+pub struct TypeRaw {
+    /// The TypeScript type expression.
+    pub ts: &'static str,
+    /// The types referenced by the expression.
+    pub deps: List<TypeExpr>,
+}
+
 /// A TypeScript identifier.
 ///
 /// Note that TypeScript's rules for valid identifiers are not checked by this
@@ -254,3 +342,25 @@ impl TypeName {
         }
     }
 }
+
+/// Returns the other member of an intersection with an empty object type
+/// (e.g. `string & {}`), which accepts the same values as that member alone.
+///
+/// TypeScript doesn't simplify these intersections, so they are used to keep
+/// a type like `string` from absorbing the string literals of a union.
+pub(crate) fn without_empty_object(members: &[TypeExpr]) -> Option<&TypeExpr> {
+    let is_empty = |object: &TypeObject| {
+        object.index_signature.is_none()
+            && object.fields.is_empty()
+            && !object.deny_unknown_fields
+    };
+    match members {
+        [member, TypeExpr::Object(object)]
+        | [TypeExpr::Object(object), member]
+            if is_empty(object) =>
+        {
+            Some(member)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,836 @@
+use crate::{
+    emit::{
+        DefNames, DefinitionFileOptions, Emit, EmitCtx, IntegerRepr, JsString,
+        ModuleStyle, Stats, TypeDef,
+    },
+    iter_def_deps::{ref_type_info, IterDefDeps},
+    type_expr::{
+        without_empty_object, DefinedTypeInfo, Ident, IndexSignature,
+        NativeTypeInfo, ObjectField, TypeArray, TypeDefinition, TypeExpr,
+        TypeInfo, TypeIntersection, TypeName, TypeObject, TypeString,
+        TypeTuple, TypeUnion,
+    },
+};
+use std::{collections::HashMap, io};
+
+/// Options for customizing the output of [`write_type_guards`].
+///
+/// The default options are:
+/// ```
+/// # use typescript_type_def::TypeGuardOptions;
+/// # let default =
+/// TypeGuardOptions {
+///     header: Some("// AUTO-GENERATED by typescript-type-def\n"),
+///     definition_file: "./types",
+///     definition_options: Default::default(),
+///     check_number_ranges: false,
+/// }
+/// # ;
+/// # assert_eq!(default, Default::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeGuardOptions<'a> {
+    /// Text to be emitted at the start of the module.
+    ///
+    /// If `Some`, the string should contain the exact content of the header as
+    /// TypeScript code (usually in the form of comments). If `None`, no header
+    /// will be added.
+    pub header: Option<&'a str>,
+    /// The import specifier of the definition file containing the types
+    /// checked by the type guards.
+    pub definition_file: &'a str,
+    /// The options that the definition file was written with using
+    /// [`write_definition_file`](crate::write_definition_file).
+    ///
+    /// These are used to reference the types in the definition file. The
+    /// [`header`](DefinitionFileOptions::header) option is ignored.
+    pub definition_options: DefinitionFileOptions<'a>,
+    /// Whether the type guards for `std` integer types (e.g. `U8`, `I32`,
+    /// `NonZeroUsize`) check that the value is an integer in the range of the
+    /// Rust type.
+    ///
+    /// Since JavaScript numbers cannot exactly represent every 64-bit integer,
+    /// no upper or lower bound is checked for 64-bit, 128-bit and
+    /// pointer-sized integers beyond their sign.
+    ///
+    /// Integers which are represented as a `string` (see [`IntegerRepr`]) are
+    /// always checked to contain decimal digits, and their sign is only
+    /// checked when this option is enabled.
+    pub check_number_ranges: bool,
+}
+
+impl Default for TypeGuardOptions<'_> {
+    fn default() -> Self {
+        Self {
+            header: Some("// AUTO-GENERATED by typescript-type-def\n"),
+            definition_file: "./types",
+            definition_options: Default::default(),
+            check_number_ranges: false,
+        }
+    }
+}
+
+/// The name of the helper function which checks for non-array objects.
+///
+/// Type guards are always named `is...`, so this can't conflict with them.
+const IS_OBJECT: &str = "__isObject";
+
+struct GuardCtx<'ctx> {
+    w: &'ctx mut dyn io::Write,
+    root_namespace: &'ctx str,
+    module_style: ModuleStyle,
+    names: &'ctx DefNames,
+    defs_by_name: HashMap<(Vec<&'static str>, &'static str), GuardName>,
+    generic_vars: &'static [Ident],
+    check_number_ranges: bool,
+    int64_repr: IntegerRepr,
+    int128_repr: IntegerRepr,
+    depth: usize,
+    uses_is_object: bool,
+}
+
+#[derive(Clone)]
+struct GuardName {
+    name: String,
+    generic_vars: usize,
+}
+
+/// The bounds of a `std` integer type.
+pub(crate) struct IntegerBounds {
+    pub(crate) min: Option<&'static str>,
+    pub(crate) max: Option<&'static str>,
+    pub(crate) non_zero: bool,
+    pub(crate) bits: u32,
+}
+
+impl IntegerBounds {
+    pub(crate) fn of(type_def: &TypeDefinition) -> Option<Self> {
+        let TypeDefinition {
+            docs: _,
+            path: _,
+            name: Ident(name),
+            generic_vars: _,
+            def: _,
+            enum_values: _,
+            brand: _,
+            std_number,
+        } = type_def;
+        if !std_number {
+            return None;
+        }
+        let (non_zero, name) = match name.strip_prefix("NonZero") {
+            Some(name) => (true, name),
+            None => (false, *name),
+        };
+        let (min, max, bits) = match name {
+            "U8" => (Some("0"), Some("255"), 8),
+            "U16" => (Some("0"), Some("65535"), 16),
+            "U32" => (Some("0"), Some("4294967295"), 32),
+            "U64" | "Usize" => (Some("0"), None, 64),
+            "U128" => (Some("0"), None, 128),
+            "I8" => (Some("-128"), Some("127"), 8),
+            "I16" => (Some("-32768"), Some("32767"), 16),
+            "I32" => (Some("-2147483648"), Some("2147483647"), 32),
+            "I64" | "Isize" => (None, None, 64),
+            "I128" => (None, None, 128),
+            _ => return None,
+        };
+        Some(Self {
+            min,
+            max,
+            non_zero,
+            bits,
+        })
+    }
+
+    /// Returns the pattern matching the decimal digits of the integers in
+    /// these bounds, checking only their sign.
+    pub(crate) fn digits_pattern(&self) -> &'static str {
+        match (self.min == Some("0"), self.non_zero) {
+            (true, false) => "^[0-9]+$",
+            (true, true) => "^0*[1-9][0-9]*$",
+            (false, false) => "^-?[0-9]+$",
+            (false, true) => "^-?0*[1-9][0-9]*$",
+        }
+    }
+}
+
+impl GuardCtx<'_> {
+    fn guard_name(&self, type_def: &'static TypeDefinition) -> String {
+        let mut guard_name = "is".to_owned();
+        for Ident(path_part) in type_def.path {
+            guard_name.push_str(path_part);
+            guard_name.push('_');
+        }
+        guard_name.push_str(self.names.get(type_def));
+        guard_name
+    }
+
+    fn emit_guard(
+        &mut self,
+        type_def: &'static TypeDefinition,
+    ) -> io::Result<()> {
+        let TypeDefinition {
+            docs: _,
+            path: _,
+            name: _,
+            generic_vars,
+            def,
+            enum_values: _,
+            brand: _,
+            std_number: _,
+        } = type_def;
+        self.generic_vars = generic_vars;
+        let guard_name = self.guard_name(type_def);
+        write!(self.w, "export function {}", guard_name)?;
+        self.emit_generic_vars(generic_vars)?;
+        write!(self.w, "(x: unknown")?;
+        for Ident(generic_var) in *generic_vars {
+            write!(self.w, ", is{0}: (x: unknown) => x is {0}", generic_var)?;
+        }
+        write!(self.w, "): x is ")?;
+        self.type_ctx().emit_def_name(type_def)?;
+        self.emit_generic_vars(generic_vars)?;
+        writeln!(self.w, " {{")?;
+        write!(self.w, "    return ")?;
+        let repr = IntegerRepr::of(type_def, self.int64_repr, self.int128_repr);
+        match IntegerBounds::of(type_def) {
+            Some(IntegerBounds {
+                min,
+                max,
+                non_zero,
+                bits: _,
+            }) if repr == Some(IntegerRepr::BigInt) => {
+                write!(self.w, "(typeof x === \"bigint\"")?;
+                if self.check_number_ranges {
+                    if let Some(min) = min {
+                        write!(self.w, " && x >= {}n", min)?;
+                    }
+                    if let Some(max) = max {
+                        write!(self.w, " && x <= {}n", max)?;
+                    }
+                    if non_zero {
+                        write!(self.w, " && x !== 0n")?;
+                    }
+                }
+                write!(self.w, ")")?;
+            }
+            Some(bounds) if repr == Some(IntegerRepr::String) => {
+                let pattern = if self.check_number_ranges {
+                    bounds.digits_pattern()
+                } else {
+                    "^-?[0-9]+$"
+                };
+                write!(
+                    self.w,
+                    "(typeof x === \"string\" && /{}/.test(x))",
+                    pattern
+                )?;
+            }
+            Some(IntegerBounds {
+                min,
+                max,
+                non_zero,
+                bits: _,
+            }) if self.check_number_ranges => {
+                write!(
+                    self.w,
+                    "(typeof x === \"number\" && Number.isInteger(x)"
+                )?;
+                if let Some(min) = min {
+                    write!(self.w, " && x >= {}", min)?;
+                }
+                if let Some(max) = max {
+                    write!(self.w, " && x <= {}", max)?;
+                }
+                if non_zero {
+                    write!(self.w, " && x !== 0")?;
+                }
+                write!(self.w, ")")?;
+            }
+            _ => self.emit_check(def, "x")?,
+        }
+        writeln!(self.w, ";")?;
+        writeln!(self.w, "}}")?;
+        Ok(())
+    }
+
+    fn emit_generic_vars(&mut self, generic_vars: &[Ident]) -> io::Result<()> {
+        if !generic_vars.is_empty() {
+            write!(self.w, "<")?;
+            for (i, Ident(generic_var)) in generic_vars.iter().enumerate() {
+                if i > 0 {
+                    write!(self.w, ", ")?;
+                }
+                write!(self.w, "{}", generic_var)?;
+            }
+            write!(self.w, ">")?;
+        }
+        Ok(())
+    }
+
+    /// Returns a context for emitting type expressions which reference the
+    /// types in the definition file.
+    fn type_ctx(&mut self) -> EmitCtx<'_> {
+        EmitCtx::new(
+            self.w,
+            Some(self.root_namespace),
+            self.module_style,
+            self.names,
+        )
+    }
+
+    /// Emits a boolean expression checking whether `value` matches `expr`.
+    ///
+    /// The value may only be an arbitrary expression if `expr` is simple (see
+    /// [`is_simple`]), otherwise it must be an identifier so that it can be
+    /// narrowed.
+    fn emit_check(&mut self, expr: &TypeExpr, value: &str) -> io::Result<()> {
+        match expr {
+            TypeExpr::Ref(type_info) => self.emit_ref_check(type_info, value),
+            TypeExpr::LazyRef(type_info) => {
+                self.emit_ref_check(type_info(), value)
+            }
+            TypeExpr::Name(TypeName {
+                path,
+                name: Ident(name),
+                generic_args,
+            }) => {
+                if path.is_empty()
+                    && generic_args.is_empty()
+                    && self
+                        .generic_vars
+                        .iter()
+                        .any(|Ident(generic_var)| generic_var == name)
+                {
+                    return write!(self.w, "is{}({})", name, value);
+                }
+                if let Some(GuardName { name, generic_vars }) = self
+                    .defs_by_name
+                    .get(&(
+                        path.iter().map(|Ident(part)| *part).collect(),
+                        *name,
+                    ))
+                    .cloned()
+                {
+                    if generic_vars == generic_args.len() {
+                        return self.emit_guard_call(
+                            &name,
+                            generic_args,
+                            value,
+                        );
+                    }
+                }
+                match (path.is_empty(), *name, *generic_args) {
+                    (true, "string" | "number" | "boolean" | "bigint", []) => {
+                        write!(
+                            self.w,
+                            "typeof {} === {}",
+                            value,
+                            JsString(name)
+                        )
+                    }
+                    (true, "null", []) => write!(self.w, "{} === null", value),
+                    (true, "undefined", []) => {
+                        write!(self.w, "{} === undefined", value)
+                    }
+                    (true, "never", []) => write!(self.w, "false"),
+                    (true, "Blob", []) => {
+                        write!(self.w, "{} instanceof Blob", value)
+                    }
+                    (true, "Record", [_key, value_type]) => {
+                        self.uses_is_object = true;
+                        write!(
+                            self.w,
+                            "({}({}) && Object.values({}).every(",
+                            IS_OBJECT, value, value
+                        )?;
+                        self.emit_guard_fn(value_type)?;
+                        write!(self.w, "))")
+                    }
+                    // types which can't be checked are assumed to match
+                    _ => write!(self.w, "true"),
+                }
+            }
+            TypeExpr::String(TypeString {
+                docs: _,
+                value: string,
+            }) => write!(self.w, "{} === {}", value, JsString(string)),
+            TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
+                write!(
+                    self.w,
+                    "(Array.isArray({0}) && {0}.length === {1}",
+                    value,
+                    elements.len()
+                )?;
+                for (i, element) in elements.iter().enumerate() {
+                    write!(self.w, " && ")?;
+                    self.emit_value_check(
+                        element,
+                        &format!("{}[{}]", value, i),
+                    )?;
+                }
+                write!(self.w, ")")
+            }
+            TypeExpr::Object(object) => self.emit_object_check(
+                object,
+                value,
+                object.deny_unknown_fields,
+            ),
+            TypeExpr::Array(TypeArray { docs: _, item }) => {
+                write!(self.w, "(Array.isArray({0}) && {0}.every(", value)?;
+                self.emit_guard_fn(item)?;
+                write!(self.w, "))")
+            }
+            TypeExpr::Union(TypeUnion { docs: _, members }) => {
+                self.emit_members_check(members, " || ", "false", value)
+            }
+            TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+                if let Some(member) = without_empty_object(members) {
+                    return self.emit_check(member, value);
+                }
+                if !members.iter().any(|member| {
+                    matches!(
+                        member,
+                        TypeExpr::Object(TypeObject {
+                            index_signature: None,
+                            deny_unknown_fields: true,
+                            ..
+                        })
+                    )
+                }) {
+                    return self
+                        .emit_members_check(members, " && ", "true", value);
+                }
+                // a closed object would reject the fields of the other
+                // members, so the keys are checked against all of them instead
+                let fields = members
+                    .iter()
+                    .map(|member| match member {
+                        TypeExpr::Object(TypeObject {
+                            index_signature: None,
+                            fields,
+                            ..
+                        }) => Some(*fields),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                write!(self.w, "(")?;
+                for (i, member) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(self.w, " && ")?;
+                    }
+                    match member {
+                        TypeExpr::Object(object) => {
+                            self.emit_object_check(object, value, false)?
+                        }
+                        _ => self.emit_check(member, value)?,
+                    }
+                }
+                if let Some(fields) = fields {
+                    write!(self.w, " && ")?;
+                    emit_known_keys_check(
+                        self.w,
+                        fields.into_iter().flatten(),
+                        value,
+                    )?;
+                }
+                write!(self.w, ")")
+            }
+            // raw types can't be checked, so they are assumed to match
+            TypeExpr::Raw(_) => write!(self.w, "true"),
+        }
+    }
+
+    /// Emits a boolean expression checking whether `value` matches `object`,
+    /// additionally rejecting properties other than its fields if `closed` is
+    /// set.
+    fn emit_object_check(
+        &mut self,
+        object: &TypeObject,
+        value: &str,
+        closed: bool,
+    ) -> io::Result<()> {
+        let TypeObject {
+            docs: _,
+            index_signature,
+            fields,
+            deny_unknown_fields: _,
+        } = object;
+        self.uses_is_object = true;
+        write!(self.w, "({}({})", IS_OBJECT, value)?;
+        for ObjectField {
+            docs: _,
+            name:
+                TypeString {
+                    docs: _,
+                    value: field_name,
+                },
+            optional,
+            r#type,
+        } in *fields
+        {
+            let field_value = format!("{}[{}]", value, JsString(field_name));
+            write!(self.w, " && ")?;
+            if *optional {
+                write!(self.w, "({} === undefined || ", field_value)?;
+            }
+            self.emit_value_check(r#type, &field_value)?;
+            if *optional {
+                write!(self.w, ")")?;
+            }
+        }
+        if let Some(IndexSignature {
+            docs: _,
+            name: _,
+            value: value_type,
+        }) = index_signature
+        {
+            write!(self.w, " && Object.values({}).every(", value)?;
+            self.emit_guard_fn(value_type)?;
+            write!(self.w, ")")?;
+        } else if closed {
+            write!(self.w, " && ")?;
+            emit_known_keys_check(self.w, fields.iter(), value)?;
+        }
+        write!(self.w, ")")
+    }
+
+    fn emit_ref_check(
+        &mut self,
+        type_info: &'static TypeInfo,
+        value: &str,
+    ) -> io::Result<()> {
+        match type_info {
+            TypeInfo::Native(NativeTypeInfo { r#ref }) => {
+                self.emit_check(r#ref, value)
+            }
+            TypeInfo::Defined(DefinedTypeInfo { def, generic_args }) => {
+                let guard_name = self.guard_name(def);
+                self.emit_guard_call(&guard_name, generic_args, value)
+            }
+        }
+    }
+
+    fn emit_guard_call(
+        &mut self,
+        guard_name: &str,
+        generic_args: &[TypeExpr],
+        value: &str,
+    ) -> io::Result<()> {
+        write!(self.w, "{}({}", guard_name, value)?;
+        for generic_arg in generic_args {
+            write!(self.w, ", ")?;
+            self.emit_guard_fn(generic_arg)?;
+        }
+        write!(self.w, ")")
+    }
+
+    fn emit_members_check(
+        &mut self,
+        members: &[TypeExpr],
+        sep: &str,
+        empty: &str,
+        value: &str,
+    ) -> io::Result<()> {
+        if members.is_empty() {
+            return write!(self.w, "{}", empty);
+        }
+        write!(self.w, "(")?;
+        for (i, member) in members.iter().enumerate() {
+            if i > 0 {
+                write!(self.w, "{}", sep)?;
+            }
+            self.emit_check(member, value)?;
+        }
+        write!(self.w, ")")
+    }
+
+    /// Emits a boolean expression checking whether the arbitrary expression
+    /// `value` matches `expr`.
+    fn emit_value_check(
+        &mut self,
+        expr: &TypeExpr,
+        value: &str,
+    ) -> io::Result<()> {
+        if is_simple(expr) {
+            self.emit_check(expr, value)
+        } else {
+            write!(self.w, "(")?;
+            self.emit_guard_fn(expr)?;
+            write!(self.w, ")({})", value)
+        }
+    }
+
+    /// Emits a type guard function expression for `expr`.
+    fn emit_guard_fn(&mut self, expr: &TypeExpr) -> io::Result<()> {
+        match ref_type_info(expr) {
+            Some(TypeInfo::Native(NativeTypeInfo { r#ref })) => {
+                return self.emit_guard_fn(r#ref);
+            }
+            Some(TypeInfo::Defined(DefinedTypeInfo {
+                def,
+                generic_args: [],
+            })) => {
+                let guard_name = self.guard_name(def);
+                return write!(self.w, "{}", guard_name);
+            }
+            _ => {}
+        }
+        match expr {
+            TypeExpr::Name(TypeName {
+                path: [],
+                name: Ident(name),
+                generic_args: [],
+            }) if self
+                .generic_vars
+                .iter()
+                .any(|Ident(generic_var)| generic_var == name) =>
+            {
+                return write!(self.w, "is{}", name);
+            }
+            TypeExpr::Name(TypeName {
+                path,
+                name: Ident(name),
+                generic_args: [],
+            }) => {
+                if let Some(GuardName {
+                    name: guard_name,
+                    generic_vars: 0,
+                }) = self.defs_by_name.get(&(
+                    path.iter().map(|Ident(part)| *part).collect(),
+                    *name,
+                )) {
+                    return write!(self.w, "{}", guard_name);
+                }
+            }
+            _ => {}
+        }
+        self.depth += 1;
+        let var = format!("x{}", self.depth);
+        write!(self.w, "({0}: unknown): {0} is ", var)?;
+        expr.emit(&mut self.type_ctx())?;
+        write!(self.w, " => ")?;
+        self.emit_check(expr, &var)?;
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+/// Emits a boolean expression checking that `value` has no properties other
+/// than the given fields.
+fn emit_known_keys_check<'a, I>(
+    w: &mut dyn io::Write,
+    fields: I,
+    value: &str,
+) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a ObjectField>,
+{
+    write!(w, "Object.keys({}).every((k) => [", value)?;
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            write!(w, ", ")?;
+        }
+        write!(w, "{}", JsString(field.name.value))?;
+    }
+    write!(w, "].includes(k))")
+}
+
+/// Returns whether the check for a type expression uses the checked value in
+/// a way which doesn't require narrowing its type.
+fn is_simple(expr: &TypeExpr) -> bool {
+    match expr {
+        TypeExpr::Ref(_) | TypeExpr::LazyRef(_) => match ref_type_info(expr) {
+            Some(TypeInfo::Native(NativeTypeInfo { r#ref })) => {
+                is_simple(r#ref)
+            }
+            Some(TypeInfo::Defined(_)) | None => true,
+        },
+        TypeExpr::Name(TypeName {
+            path,
+            name: Ident(name),
+            generic_args: _,
+        }) => !(path.is_empty() && *name == "Record"),
+        TypeExpr::String(_) | TypeExpr::Raw(_) => true,
+        TypeExpr::Tuple(_) | TypeExpr::Object(_) | TypeExpr::Array(_) => false,
+        TypeExpr::Union(TypeUnion {
+            docs: _,
+            members: exprs,
+        }) => exprs.iter().all(is_simple),
+        TypeExpr::Intersection(TypeIntersection {
+            docs: _,
+            members: exprs,
+        }) => match without_empty_object(exprs) {
+            Some(expr) => is_simple(expr),
+            None => exprs.iter().all(is_simple),
+        },
+    }
+}
+
+/// Writes a TypeScript module containing runtime type guards for `T` and all
+/// of its transitive dependencies to the given writer.
+///
+/// For every type definition `Foo`, the module exports a function
+/// `isFoo(x: unknown): x is Foo` which checks whether a value (such as one
+/// parsed from untrusted JSON) matches the type. Type definitions with a
+/// namespace have the namespace path prefixed to the function name, joined
+/// with underscores (e.g. `isx_y_Foo`). Type guards for generic types take an
+/// additional type guard argument for each generic type parameter.
+///
+/// The type guards reference the types in a definition file written by
+/// [`write_definition_file`](crate::write_definition_file), which is imported
+/// from the [`definition_file`](TypeGuardOptions::definition_file). The
+/// [`definition_options`](TypeGuardOptions::definition_options) must match the
+/// options used to write the definition file.
+///
+/// Object keys of `Record` types are not checked, and types which are not
+/// known to this library (such as those specified with
+/// `#[type_def(type_of = "T")]` for types without a `TypeDef` impl) are
+/// assumed to always match.
+///
+/// # Example
+/// ```
+/// use serde::Serialize;
+/// use typescript_type_def::{write_type_guards, TypeDef};
+///
+/// #[derive(Serialize, TypeDef)]
+/// struct User {
+///     name: String,
+///     #[serde(skip_serializing_if = "Option::is_none")]
+///     email: Option<String>,
+///     tags: Vec<String>,
+/// }
+///
+/// let ts_module = {
+///     let mut buf = Vec::new();
+///     write_type_guards::<_, User>(&mut buf, Default::default()).unwrap();
+///     String::from_utf8(buf).unwrap()
+/// };
+/// assert_eq!(
+///     ts_module,
+///     r#"// AUTO-GENERATED by typescript-type-def
+///
+/// import type types from "./types";
+///
+/// function __isObject(x: unknown): x is Record<string, unknown> {
+///     return typeof x === "object" && x !== null && !Array.isArray(x);
+/// }
+///
+/// export function isUser(x: unknown): x is types.User {
+///     return (__isObject(x) && typeof x["name"] === "string" && (x["email"] === undefined || typeof x["email"] === "string") && ((x1: unknown): x1 is (string)[] => (Array.isArray(x1) && x1.every((x2: unknown): x2 is string => typeof x2 === "string")))(x["tags"]));
+/// }
+/// "#
+/// );
+/// ```
+pub fn write_type_guards<W, T>(
+    writer: W,
+    options: TypeGuardOptions<'_>,
+) -> io::Result<Stats>
+where
+    W: io::Write,
+    T: TypeDef + ?Sized,
+{
+    write_type_guards_from_type_infos(writer, options, &[&T::INFO])
+}
+
+/// Writes a TypeScript module containing runtime type guards for the given
+/// list of type info values to the given writer.
+///
+/// The type info values can be obtained using [`TypeDef::INFO`] on a type.
+///
+/// See [`write_type_guards`] for details on the produced type guards.
+pub fn write_type_guards_from_type_infos<W>(
+    mut writer: W,
+    options: TypeGuardOptions<'_>,
+    type_infos: &[&'static TypeInfo],
+) -> io::Result<Stats>
+where
+    W: io::Write,
+{
+    let TypeGuardOptions {
+        header,
+        definition_file,
+        definition_options,
+        check_number_ranges,
+    } = options;
+    let defs = IterDefDeps::new(type_infos).collect::<Vec<_>>();
+    let names = DefNames::resolve(
+        &defs,
+        definition_options.module_style,
+        definition_options.name_conflicts,
+    )?;
+    let root_namespace = match definition_options.module_style {
+        ModuleStyle::Namespaces => definition_options.root_namespace,
+        ModuleStyle::Flat => None,
+    };
+
+    let mut body = Vec::new();
+    let mut ctx = GuardCtx {
+        w: &mut body,
+        root_namespace: root_namespace.unwrap_or("types"),
+        module_style: definition_options.module_style,
+        names: &names,
+        defs_by_name: HashMap::new(),
+        generic_vars: &[],
+        check_number_ranges,
+        int64_repr: definition_options.int64_repr,
+        int128_repr: definition_options.int128_repr,
+        depth: 0,
+        uses_is_object: false,
+    };
+    for &type_def in &defs {
+        let guard_name = GuardName {
+            name: ctx.guard_name(type_def),
+            generic_vars: type_def.generic_vars.len(),
+        };
+        ctx.defs_by_name
+            .entry((
+                type_def.path.iter().map(|Ident(part)| *part).collect(),
+                type_def.name.0,
+            ))
+            .or_insert(guard_name);
+    }
+    for &type_def in &defs {
+        ctx.emit_guard(type_def)?;
+    }
+    let uses_is_object = ctx.uses_is_object;
+
+    if let Some(header) = header {
+        writeln!(writer, "{}", header)?;
+    }
+    if !defs.is_empty() {
+        match root_namespace {
+            Some(root_namespace) => writeln!(
+                writer,
+                "import type {} from {};",
+                root_namespace,
+                JsString(definition_file)
+            )?,
+            None => writeln!(
+                writer,
+                "import type * as types from {};",
+                JsString(definition_file)
+            )?,
+        }
+        writeln!(writer)?;
+    }
+    if uses_is_object {
+        writeln!(
+            writer,
+            "function {}(x: unknown): x is Record<string, unknown> {{",
+            IS_OBJECT
+        )?;
+        writeln!(
+            writer,
+            "    return typeof x === \"object\" && x !== null && \
+             !Array.isArray(x);"
+        )?;
+        writeln!(writer, "}}")?;
+        writeln!(writer)?;
+    }
+    writer.write_all(&body)?;
+    Ok(Stats {
+        type_definitions: defs.len(),
+    })
+}
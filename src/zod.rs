@@ -0,0 +1,745 @@
+use crate::{
+    emit::{
+        brand_name, DefNames, EmitCtx, IntegerRepr, JsString, ModuleStyle,
+        NameConflicts, Stats, TypeDef,
+    },
+    iter_def_deps::{hash_type_definition, ref_type_info, IterDefDeps},
+    type_expr::{
+        without_empty_object, DefinedTypeInfo, Ident, IndexSignature,
+        NativeTypeInfo, ObjectField, TypeArray, TypeDefinition, TypeExpr,
+        TypeInfo, TypeIntersection, TypeName, TypeObject, TypeString,
+        TypeTuple, TypeUnion,
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+/// Options for customizing the output of [`write_zod_schemas`].
+///
+/// The default options are:
+/// ```
+/// # use typescript_type_def::{
+/// #     IntegerRepr, NameConflicts, ZodSchemaOptions,
+/// # };
+/// # let default =
+/// ZodSchemaOptions {
+///     header: Some("// AUTO-GENERATED by typescript-type-def\n"),
+///     name_conflicts: NameConflicts::Error,
+///     int64_repr: IntegerRepr::Number,
+///     int128_repr: IntegerRepr::String,
+/// }
+/// # ;
+/// # assert_eq!(default, Default::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZodSchemaOptions<'a> {
+    /// Text to be emitted at the start of the module.
+    ///
+    /// If `Some`, the string should contain the exact content of the header as
+    /// TypeScript code (usually in the form of comments). If `None`, no header
+    /// will be added.
+    pub header: Option<&'a str>,
+    /// How to handle distinct type definitions which have the same name and
+    /// namespace.
+    pub name_conflicts: NameConflicts,
+    /// How the aliases of 64-bit integer types are validated.
+    ///
+    /// See the [`int64_repr`](crate::DefinitionFileOptions::int64_repr) option of
+    /// [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+    pub int64_repr: IntegerRepr,
+    /// How the aliases of 128-bit integer types are validated.
+    ///
+    /// See the [`int128_repr`](crate::DefinitionFileOptions::int128_repr) option of
+    /// [`DefinitionFileOptions`](crate::DefinitionFileOptions).
+    pub int128_repr: IntegerRepr,
+}
+
+impl Default for ZodSchemaOptions<'_> {
+    fn default() -> Self {
+        Self {
+            header: Some("// AUTO-GENERATED by typescript-type-def\n"),
+            name_conflicts: NameConflicts::Error,
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+        }
+    }
+}
+
+struct ZodCtx<'ctx> {
+    w: &'ctx mut dyn io::Write,
+    names: &'ctx DefNames,
+    defs_by_name: HashMap<(Vec<&'static str>, &'static str), SchemaRef>,
+    generic_vars: &'static [Ident],
+    int64_repr: IntegerRepr,
+    int128_repr: IntegerRepr,
+    emitted: HashSet<u64>,
+}
+
+#[derive(Clone, Copy)]
+struct SchemaRef {
+    type_def: &'static TypeDefinition,
+    hash: u64,
+}
+
+impl ZodCtx<'_> {
+    fn schema_name(&self, type_def: &'static TypeDefinition) -> String {
+        let mut schema_name = String::new();
+        for Ident(path_part) in type_def.path {
+            schema_name.push_str(path_part);
+            schema_name.push('_');
+        }
+        schema_name.push_str(self.names.get(type_def));
+        schema_name.push_str("Schema");
+        schema_name
+    }
+
+    fn emit_schema(
+        &mut self,
+        type_def: &'static TypeDefinition,
+        is_recursive: bool,
+    ) -> io::Result<()> {
+        let TypeDefinition {
+            docs: _,
+            path: _,
+            name: _,
+            generic_vars,
+            def,
+            enum_values: _,
+            brand,
+            std_number: _,
+        } = type_def;
+        self.generic_vars = generic_vars;
+        let schema_name = self.schema_name(type_def);
+        if generic_vars.is_empty() && !is_recursive {
+            write!(self.w, "export const {} = ", schema_name)?;
+            match IntegerRepr::of(type_def, self.int64_repr, self.int128_repr) {
+                Some(repr) => write!(self.w, "z.{}()", repr.keyword())?,
+                None => self.emit_expr(def)?,
+            }
+            if *brand {
+                write!(
+                    self.w,
+                    ".brand<{}>()",
+                    JsString(&brand_name(self.names, type_def))
+                )?;
+            }
+            writeln!(self.w, ";")?;
+            write!(self.w, "export type ")?;
+            self.type_ctx().emit_def_name(type_def)?;
+            writeln!(self.w, " = z.infer<typeof {}>;", schema_name)?;
+        } else {
+            // the inferred type of generic and recursive schemas can't be
+            // named, so the type is emitted explicitly
+            self.type_ctx().emit_type_def([type_def])?;
+            if generic_vars.is_empty() {
+                write!(self.w, "export const {}: z.ZodType<", schema_name)?;
+                self.type_ctx().emit_def_name(type_def)?;
+                write!(self.w, "> = ")?;
+                self.emit_expr(def)?;
+                writeln!(self.w, ";")?;
+            } else {
+                write!(self.w, "export function {}", schema_name)?;
+                self.emit_generic_vars()?;
+                write!(self.w, "(")?;
+                for (i, Ident(generic_var)) in generic_vars.iter().enumerate() {
+                    if i > 0 {
+                        write!(self.w, ", ")?;
+                    }
+                    write!(self.w, "{0}: z.ZodType<{0}>", generic_var)?;
+                }
+                write!(self.w, "): z.ZodType<")?;
+                self.type_ctx().emit_def_name(type_def)?;
+                self.emit_generic_vars()?;
+                writeln!(self.w, "> {{")?;
+                write!(self.w, "    return ")?;
+                self.emit_expr(def)?;
+                writeln!(self.w, ";")?;
+                writeln!(self.w, "}}")?;
+            }
+        }
+        self.emitted.insert(hash_type_definition(type_def));
+        Ok(())
+    }
+
+    fn emit_generic_vars(&mut self) -> io::Result<()> {
+        let generic_vars = self.generic_vars;
+        if !generic_vars.is_empty() {
+            write!(self.w, "<")?;
+            for (i, Ident(generic_var)) in generic_vars.iter().enumerate() {
+                if i > 0 {
+                    write!(self.w, ", ")?;
+                }
+                write!(self.w, "{}", generic_var)?;
+            }
+            write!(self.w, ">")?;
+        }
+        Ok(())
+    }
+
+    /// Returns a context for emitting type expressions which reference the
+    /// types in this module.
+    fn type_ctx(&mut self) -> EmitCtx<'_> {
+        EmitCtx::new(self.w, None, ModuleStyle::Flat, self.names)
+            .with_integer_reprs(self.int64_repr, self.int128_repr)
+            .without_brands()
+    }
+
+    fn emit_expr(&mut self, expr: &TypeExpr) -> io::Result<()> {
+        match expr {
+            TypeExpr::Ref(type_info) => self.emit_ref(type_info),
+            TypeExpr::LazyRef(type_info) => self.emit_ref(type_info()),
+            TypeExpr::Name(TypeName {
+                path,
+                name: Ident(name),
+                generic_args,
+            }) => {
+                if path.is_empty()
+                    && generic_args.is_empty()
+                    && self
+                        .generic_vars
+                        .iter()
+                        .any(|Ident(generic_var)| generic_var == name)
+                {
+                    return write!(self.w, "{}", name);
+                }
+                if let Some(SchemaRef { type_def, hash }) = self
+                    .defs_by_name
+                    .get(&(
+                        path.iter().map(|Ident(part)| *part).collect(),
+                        *name,
+                    ))
+                    .copied()
+                {
+                    if type_def.generic_vars.len() == generic_args.len() {
+                        return self.emit_schema_ref(
+                            type_def,
+                            hash,
+                            generic_args,
+                        );
+                    }
+                }
+                match (path.is_empty(), *name, *generic_args) {
+                    (
+                        true,
+                        "string" | "number" | "boolean" | "bigint" | "null"
+                        | "undefined" | "unknown" | "any" | "never",
+                        [],
+                    ) => write!(self.w, "z.{}()", name),
+                    (true, "Blob", []) => write!(self.w, "z.instanceof(Blob)"),
+                    (true, "Record", [_key, value]) => {
+                        // JSON object keys are always strings
+                        write!(self.w, "z.record(z.string(), ")?;
+                        self.emit_expr(value)?;
+                        write!(self.w, ")")
+                    }
+                    // types which can't be checked are assumed to match
+                    _ => write!(self.w, "z.unknown()"),
+                }
+            }
+            TypeExpr::String(TypeString { docs: _, value }) => {
+                write!(self.w, "z.literal({})", JsString(value))
+            }
+            TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
+                write!(self.w, "z.tuple([")?;
+                self.emit_list(elements)?;
+                write!(self.w, "])")
+            }
+            TypeExpr::Object(object) => {
+                self.emit_object([object], object.deny_unknown_fields)
+            }
+            TypeExpr::Array(TypeArray { docs: _, item }) => {
+                write!(self.w, "z.array(")?;
+                self.emit_expr(item)?;
+                write!(self.w, ")")
+            }
+            TypeExpr::Union(TypeUnion { docs: _, members }) => match members {
+                [] => write!(self.w, "z.never()"),
+                [member] => self.emit_expr(member),
+                _ => {
+                    if let Some(tag) = discriminator(members) {
+                        write!(
+                            self.w,
+                            "z.discriminatedUnion({}, [",
+                            JsString(tag)
+                        )?;
+                    } else {
+                        write!(self.w, "z.union([")?;
+                    }
+                    self.emit_list(members)?;
+                    write!(self.w, "])")
+                }
+            },
+            TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+                if let Some(member) = without_empty_object(members) {
+                    return self.emit_expr(member);
+                }
+                if let Some(objects) =
+                    members.iter().map(as_object).collect::<Option<Vec<_>>>()
+                {
+                    if !objects.is_empty() {
+                        let closed = objects
+                            .iter()
+                            .any(|object| object.deny_unknown_fields);
+                        return self.emit_object(objects, closed);
+                    }
+                }
+                match members {
+                    [] => write!(self.w, "z.unknown()"),
+                    [first, rest @ ..] => {
+                        // a strict object would reject the properties of the
+                        // other members, so objects are never strict here
+                        self.emit_intersection_member(first)?;
+                        for member in rest {
+                            write!(self.w, ".and(")?;
+                            self.emit_intersection_member(member)?;
+                            write!(self.w, ")")?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            // raw types can't be checked, so they are assumed to match
+            TypeExpr::Raw(_) => write!(self.w, "z.unknown()"),
+        }
+    }
+
+    fn emit_ref(&mut self, type_info: &'static TypeInfo) -> io::Result<()> {
+        match type_info {
+            TypeInfo::Native(NativeTypeInfo { r#ref }) => self.emit_expr(r#ref),
+            TypeInfo::Defined(DefinedTypeInfo { def, generic_args }) => self
+                .emit_schema_ref(def, hash_type_definition(def), generic_args),
+        }
+    }
+
+    fn emit_schema_ref(
+        &mut self,
+        type_def: &'static TypeDefinition,
+        hash: u64,
+        generic_args: &[TypeExpr],
+    ) -> io::Result<()> {
+        // schemas which are not defined yet are referenced lazily, which is
+        // needed for recursive types
+        let is_lazy = !self.emitted.contains(&hash);
+        if is_lazy {
+            write!(self.w, "z.lazy(() => ")?;
+        }
+        let schema_name = self.schema_name(type_def);
+        write!(self.w, "{}", schema_name)?;
+        if !generic_args.is_empty() {
+            write!(self.w, "(")?;
+            self.emit_list(generic_args)?;
+            write!(self.w, ")")?;
+        }
+        if is_lazy {
+            write!(self.w, ")")?;
+        }
+        Ok(())
+    }
+
+    fn emit_intersection_member(&mut self, expr: &TypeExpr) -> io::Result<()> {
+        match expr {
+            TypeExpr::Object(object) => self.emit_object([object], false),
+            _ => self.emit_expr(expr),
+        }
+    }
+
+    /// Emits the fields of the given objects as a single object schema, which
+    /// rejects unknown keys if `closed` is set.
+    fn emit_object<'a, I>(&mut self, objects: I, closed: bool) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a TypeObject>,
+    {
+        let mut index_signature = None;
+        let mut fields = Vec::new();
+        for TypeObject {
+            docs: _,
+            index_signature: object_index_signature,
+            fields: object_fields,
+            deny_unknown_fields: _,
+        } in objects
+        {
+            index_signature = index_signature.or(*object_index_signature);
+            fields.extend(object_fields.iter());
+        }
+        if fields.is_empty() {
+            if let Some(IndexSignature {
+                docs: _,
+                name: _,
+                value,
+            }) = index_signature
+            {
+                write!(self.w, "z.record(z.string(), ")?;
+                self.emit_expr(value)?;
+                return write!(self.w, ")");
+            }
+        }
+        write!(self.w, "z.object({{")?;
+        for (
+            i,
+            ObjectField {
+                docs: _,
+                name:
+                    TypeString {
+                        docs: _,
+                        value: name,
+                    },
+                optional,
+                r#type,
+            },
+        ) in fields.into_iter().enumerate()
+        {
+            if i > 0 {
+                write!(self.w, ",")?;
+            }
+            write!(self.w, " {}: ", JsString(name))?;
+            self.emit_expr(&r#type)?;
+            if optional {
+                write!(self.w, ".optional()")?;
+            }
+        }
+        write!(self.w, " }})")?;
+        if let Some(IndexSignature {
+            docs: _,
+            name: _,
+            value,
+        }) = index_signature
+        {
+            write!(self.w, ".catchall(")?;
+            self.emit_expr(value)?;
+            write!(self.w, ")")?;
+        } else if closed {
+            write!(self.w, ".strict()")?;
+        }
+        Ok(())
+    }
+
+    fn emit_list(&mut self, exprs: &[TypeExpr]) -> io::Result<()> {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                write!(self.w, ", ")?;
+            }
+            self.emit_expr(expr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the object described by a type expression, if it is an object
+/// without an index signature.
+fn as_object(expr: &TypeExpr) -> Option<&TypeObject> {
+    match expr {
+        TypeExpr::Object(
+            object @ TypeObject {
+                docs: _,
+                index_signature: None,
+                fields: _,
+                deny_unknown_fields: _,
+            },
+        ) => Some(object),
+        _ => None,
+    }
+}
+
+/// Returns the fields of a union member which is an object or an intersection
+/// of objects.
+fn union_member_fields(expr: &TypeExpr) -> Option<Vec<&ObjectField>> {
+    match expr {
+        TypeExpr::Object(_) => {
+            as_object(expr).map(|object| object.fields.iter().collect())
+        }
+        TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+            let mut fields = Vec::new();
+            for member in *members {
+                fields.extend(as_object(member)?.fields);
+            }
+            Some(fields)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the name of a field which can be used to discriminate between the
+/// members of a union, if there is one.
+///
+/// Such a field is present in every member with a distinct string literal
+/// type, such as the tag field of an enum with `#[serde(tag = "...")]`.
+pub(crate) fn discriminator(members: &[TypeExpr]) -> Option<&'static str> {
+    let members = members
+        .iter()
+        .map(union_member_fields)
+        .collect::<Option<Vec<_>>>()?;
+    let (first, rest) = members.split_first()?;
+    let tag_value = |fields: &[&ObjectField], tag: &str| {
+        fields.iter().find_map(
+            |ObjectField {
+                 docs: _,
+                 name:
+                     TypeString {
+                         docs: _,
+                         value: name,
+                     },
+                 optional,
+                 r#type,
+             }| match r#type {
+                TypeExpr::String(TypeString { docs: _, value })
+                    if *name == tag && !optional =>
+                {
+                    Some(*value)
+                }
+                _ => None,
+            },
+        )
+    };
+    first.iter().find_map(|field| {
+        let tag = field.name.value;
+        let mut values = HashSet::new();
+        values.insert(tag_value(first, tag)?);
+        rest.iter()
+            .all(|fields| {
+                matches!(
+                    tag_value(fields, tag),
+                    Some(value) if values.insert(value)
+                )
+            })
+            .then_some(tag)
+    })
+}
+
+/// Returns the hashes of the type definitions directly referenced by a type
+/// expression.
+fn collect_deps(
+    expr: &TypeExpr,
+    defs_by_name: &HashMap<(Vec<&'static str>, &'static str), SchemaRef>,
+    deps: &mut HashSet<u64>,
+) {
+    if let Some(type_info) = ref_type_info(expr) {
+        match type_info {
+            TypeInfo::Native(NativeTypeInfo { r#ref }) => {
+                collect_deps(r#ref, defs_by_name, deps);
+            }
+            TypeInfo::Defined(DefinedTypeInfo { def, generic_args }) => {
+                deps.insert(hash_type_definition(def));
+                for generic_arg in *generic_args {
+                    collect_deps(generic_arg, defs_by_name, deps);
+                }
+            }
+        }
+        return;
+    }
+    let visit = |exprs: &[TypeExpr], deps: &mut HashSet<u64>| {
+        for expr in exprs {
+            collect_deps(expr, defs_by_name, deps);
+        }
+    };
+    match expr {
+        TypeExpr::Ref(_) | TypeExpr::LazyRef(_) => {}
+        TypeExpr::Name(TypeName {
+            path,
+            name: Ident(name),
+            generic_args,
+        }) => {
+            if let Some(SchemaRef { type_def: _, hash }) = defs_by_name
+                .get(&(path.iter().map(|Ident(part)| *part).collect(), *name))
+            {
+                deps.insert(*hash);
+            }
+            visit(generic_args, deps);
+        }
+        TypeExpr::String(_) => {}
+        TypeExpr::Tuple(TypeTuple { docs: _, elements }) => {
+            visit(elements, deps)
+        }
+        TypeExpr::Object(TypeObject {
+            docs: _,
+            index_signature,
+            fields,
+            deny_unknown_fields: _,
+        }) => {
+            if let Some(IndexSignature {
+                docs: _,
+                name: _,
+                value,
+            }) = index_signature
+            {
+                visit(std::slice::from_ref(*value), deps);
+            }
+            for ObjectField {
+                docs: _,
+                name: _,
+                optional: _,
+                r#type,
+            } in *fields
+            {
+                visit(std::slice::from_ref(r#type), deps);
+            }
+        }
+        TypeExpr::Array(TypeArray { docs: _, item }) => {
+            visit(std::slice::from_ref(*item), deps);
+        }
+        TypeExpr::Union(TypeUnion { docs: _, members })
+        | TypeExpr::Intersection(TypeIntersection { docs: _, members }) => {
+            visit(members, deps);
+        }
+        // raw types are not checked, so their dependencies are not used
+        TypeExpr::Raw(_) => {}
+    }
+}
+
+/// Returns the hashes of the given type definitions which refer to
+/// themselves, directly or through other definitions.
+fn recursive_defs(
+    defs: &[&'static TypeDefinition],
+    defs_by_name: &HashMap<(Vec<&'static str>, &'static str), SchemaRef>,
+) -> HashSet<u64> {
+    let deps = defs
+        .iter()
+        .map(|type_def| {
+            let mut deps = HashSet::new();
+            collect_deps(&type_def.def, defs_by_name, &mut deps);
+            (hash_type_definition(type_def), deps)
+        })
+        .collect::<HashMap<_, _>>();
+    deps.keys()
+        .copied()
+        .filter(|&hash| {
+            let mut visited = HashSet::new();
+            let mut stack = deps[&hash].iter().copied().collect::<Vec<_>>();
+            while let Some(dep) = stack.pop() {
+                if dep == hash {
+                    return true;
+                }
+                if visited.insert(dep) {
+                    stack.extend(deps.get(&dep).into_iter().flatten());
+                }
+            }
+            false
+        })
+        .collect()
+}
+
+/// Writes a TypeScript module containing [Zod](https://zod.dev/) schemas for
+/// `T` and all of its transitive dependencies to the given writer.
+///
+/// For every type definition `Foo`, the module exports a schema `FooSchema`
+/// and a type `Foo` which is inferred from the schema. Type definitions with a
+/// namespace have the namespace path prefixed to their names, joined with
+/// underscores (e.g. `x_y_FooSchema`).
+///
+/// Unions of objects which all have a field with a distinct string literal
+/// type (such as enums with `#[serde(tag = "...")]`) use
+/// `z.discriminatedUnion`, and intersections of objects (such as those
+/// produced by `#[serde(flatten)]`) are merged into a single object schema.
+///
+/// Generic types are emitted as schema factory functions which take a schema
+/// for each generic type parameter. Since the inferred types of generic and
+/// recursive schemas can't be named, their types are emitted explicitly
+/// instead of being inferred, and recursive references use `z.lazy`.
+///
+/// Branded types (see
+/// [`TypeDefinition::brand`](crate::type_expr::TypeDefinition::brand)) use
+/// Zod's `.brand()`, except for generic and recursive types which are not
+/// branded.
+///
+/// # Example
+/// ```
+/// use serde::Serialize;
+/// use typescript_type_def::{write_zod_schemas, TypeDef};
+///
+/// #[derive(Serialize, TypeDef)]
+/// #[serde(tag = "type")]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     Point,
+/// }
+///
+/// let ts_module = {
+///     let mut buf = Vec::new();
+///     write_zod_schemas::<_, Shape>(&mut buf, Default::default()).unwrap();
+///     String::from_utf8(buf).unwrap()
+/// };
+/// assert_eq!(
+///     ts_module,
+///     r#"// AUTO-GENERATED by typescript-type-def
+///
+/// import { z } from "zod";
+///
+/// export const F64Schema = z.number();
+/// export type F64 = z.infer<typeof F64Schema>;
+/// export const ShapeSchema = z.discriminatedUnion("type", [z.object({ "type": z.literal("Circle"), "radius": F64Schema }), z.object({ "type": z.literal("Point") })]);
+/// export type Shape = z.infer<typeof ShapeSchema>;
+/// "#
+/// );
+/// ```
+pub fn write_zod_schemas<W, T>(
+    writer: W,
+    options: ZodSchemaOptions<'_>,
+) -> io::Result<Stats>
+where
+    W: io::Write,
+    T: TypeDef + ?Sized,
+{
+    write_zod_schemas_from_type_infos(writer, options, &[&T::INFO])
+}
+
+/// Writes a TypeScript module containing [Zod](https://zod.dev/) schemas for
+/// the given list of type info values to the given writer.
+///
+/// The type info values can be obtained using [`TypeDef::INFO`] on a type.
+///
+/// See [`write_zod_schemas`] for details on the produced schemas.
+pub fn write_zod_schemas_from_type_infos<W>(
+    mut writer: W,
+    options: ZodSchemaOptions<'_>,
+    type_infos: &[&'static TypeInfo],
+) -> io::Result<Stats>
+where
+    W: io::Write,
+{
+    let ZodSchemaOptions {
+        header,
+        name_conflicts,
+        int64_repr,
+        int128_repr,
+    } = options;
+    let defs = IterDefDeps::new(type_infos).collect::<Vec<_>>();
+    // schemas are named after their flattened namespace path and name
+    let names = DefNames::resolve(&defs, ModuleStyle::Flat, name_conflicts)?;
+    let mut defs_by_name = HashMap::new();
+    for &type_def in &defs {
+        defs_by_name
+            .entry((
+                type_def.path.iter().map(|Ident(part)| *part).collect(),
+                type_def.name.0,
+            ))
+            .or_insert(SchemaRef {
+                type_def,
+                hash: hash_type_definition(type_def),
+            });
+    }
+    let recursive_defs = recursive_defs(&defs, &defs_by_name);
+
+    if let Some(header) = header {
+        writeln!(writer, "{}", header)?;
+    }
+    writeln!(writer, "import {{ z }} from \"zod\";")?;
+    writeln!(writer)?;
+    let mut ctx = ZodCtx {
+        w: &mut writer,
+        names: &names,
+        defs_by_name,
+        generic_vars: &[],
+        int64_repr,
+        int128_repr,
+        emitted: HashSet::new(),
+    };
+    for &type_def in &defs {
+        let is_recursive =
+            recursive_defs.contains(&hash_type_definition(type_def));
+        ctx.emit_schema(type_def, is_recursive)?;
+    }
+    Ok(Stats {
+        type_definitions: defs.len(),
+    })
+}
@@ -1,13 +1,20 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use typescript_type_def::{
     type_expr::{DefinedTypeInfo, Ident, TypeDefinition, TypeExpr, TypeInfo},
-    write_definition_file, DefinitionFileOptions, TypeDef,
+    write_definition_file, write_definition_file_with_inputs,
+    DefinitionFileOptions, IntegerRepr, ModuleStyle, NameConflicts, TypeDef,
 };
 
 static TEST_OPTIONS: DefinitionFileOptions<'_> = DefinitionFileOptions {
     header: None,
     root_namespace: Some("types"),
+    module_style: ModuleStyle::Namespaces,
+    name_conflicts: NameConflicts::Error,
+    input_suffix: "Input",
+    int64_repr: IntegerRepr::Number,
+    int128_repr: IntegerRepr::String,
+    brand_numbers: false,
 };
 
 fn test_emit<T>() -> String
@@ -19,6 +26,15 @@ where
     String::from_utf8(buf).unwrap()
 }
 
+fn test_emit_with_inputs<T>() -> String
+where
+    T: TypeDef,
+{
+    let mut buf = Vec::new();
+    write_definition_file_with_inputs::<_, T>(&mut buf, TEST_OPTIONS).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
 macro_rules! assert_eq_str {
     ($actual:expr, $expected:expr) => {{
         let actual = $actual;
@@ -42,6 +58,9 @@ fn emit() {
                 name: Ident("Test"),
                 generic_vars: &[],
                 def: TypeExpr::Ref(&Inner::INFO),
+                enum_values: None,
+                brand: false,
+                std_number: false,
             },
             generic_args: &[],
         });
@@ -704,6 +723,59 @@ export namespace types {
         );
     }
 
+    #[test]
+    fn recursive() {
+        #[derive(Serialize, TypeDef)]
+        struct Tree<T> {
+            value: T,
+            children: Vec<Tree<T>>,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        enum Expr {
+            Lit(usize),
+            Block(Box<Block>),
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Block {
+            exprs: Vec<Expr>,
+            parent: Option<Box<Block>>,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: Tree<String>,
+            b: Expr,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type Tree<T> = {
+        "value": T;
+        "children": (types.Tree<T>)[];
+    };
+    export type Usize = number;
+    export type Block = {
+        "exprs": (types.Expr)[];
+        "parent": (types.Block | null);
+    };
+    export type Expr = ({
+        "Lit": types.Usize;
+    } | {
+        "Block": types.Block;
+    });
+    export type Test = {
+        "a": types.Tree<string>;
+        "b": types.Expr;
+    };
+}
+"#
+        );
+    }
+
     #[test]
     fn default() {
         #[derive(Serialize, TypeDef)]
@@ -719,10 +791,14 @@ export namespace types {
         }
 
         assert_eq_str!(
-            test_emit::<Test>(),
+            test_emit_with_inputs::<Test>(),
             r#"export default types;
 export namespace types {
     export type Test = {
+        "a": string;
+        "b": string;
+    };
+    export type TestInput = {
         "a"?: string;
         "b"?: string;
     };
@@ -732,161 +808,2904 @@ export namespace types {
     }
 
     #[test]
-    fn foreign_field() {
-        #[derive(Serialize)]
-        #[serde(transparent)]
-        struct ExternalString(String);
-
-        #[derive(Serialize, TypeDef)]
-        #[serde(transparent)]
-        struct ExternalStringWrapper(
-            #[type_def(type_of = "String")] ExternalString,
-        );
+    fn directions() {
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Inner {
+            #[serde(skip_serializing)]
+            secret: String,
+            #[serde(skip_deserializing)]
+            id: u32,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            note: Option<String>,
+            parent: Option<u32>,
+        }
 
-        #[derive(Serialize, TypeDef)]
-        struct Test {
-            #[type_def(type_of = "String")]
-            a: ExternalString,
-            b: ExternalStringWrapper,
-            c: String,
-            #[type_def(type_of = "i16")]
-            d: usize,
-            #[type_def(flatten, type_of = "Test3")]
-            e: Test2,
+        #[derive(Serialize, Deserialize, TypeDef)]
+        enum Kind {
+            A,
+            #[serde(skip_serializing)]
+            B,
+            #[serde(skip_deserializing)]
+            C,
         }
 
-        #[derive(Serialize)]
-        struct Test2 {
-            foo: String,
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Plain {
+            a: String,
         }
 
-        #[derive(Serialize, TypeDef)]
-        struct Test3 {
-            bar: String,
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Test {
+            inner: Vec<Inner>,
+            kind: Kind,
+            plain: Plain,
         }
 
         assert_eq_str!(
             test_emit::<Test>(),
             r#"export default types;
 export namespace types {
-    export type Test3 = {
-        "bar": string;
+    export type U32 = number;
+    export type Inner = {
+        "id": types.U32;
+        "note"?: string;
+        "parent": (types.U32 | null);
     };
-    export type ExternalStringWrapper = string;
-    export type I16 = number;
-    export type Test = (types.Test3 & {
+    export type Kind = ("A" | "C");
+    export type Plain = {
         "a": string;
-        "b": types.ExternalStringWrapper;
-        "c": string;
-        "d": types.I16;
-    });
+    };
+    export type Test = {
+        "inner": (types.Inner)[];
+        "kind": types.Kind;
+        "plain": types.Plain;
+    };
+}
+"#
+        );
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U32 = number;
+    export type Inner = {
+        "id": types.U32;
+        "note"?: string;
+        "parent": (types.U32 | null);
+    };
+    export type Kind = ("A" | "C");
+    export type Plain = {
+        "a": string;
+    };
+    export type Test = {
+        "inner": (types.Inner)[];
+        "kind": types.Kind;
+        "plain": types.Plain;
+    };
+    export type InnerInput = {
+        "secret": string;
+        "note"?: (string | null);
+        "parent"?: (types.U32 | null);
+    };
+    export type KindInput = ("A" | "B");
+    export type TestInput = {
+        "inner": (types.InnerInput)[];
+        "kind": types.KindInput;
+        "plain": types.Plain;
+    };
 }
 "#
         );
     }
 
     #[test]
-    fn no_root_namespace() {
-        #[derive(Serialize, TypeDef)]
-        struct Test {
-            a: usize,
+    fn alias_other() {
+        #[derive(Serialize, Deserialize, TypeDef)]
+        enum External {
+            #[serde(alias = "a", alias = "first")]
+            A,
+            B(u8),
+            #[serde(other)]
+            Unknown,
         }
 
-        let mut buf = Vec::new();
-        let options = DefinitionFileOptions {
-            header: None,
-            root_namespace: None,
-        };
-        write_definition_file::<_, Test>(&mut buf, options).unwrap();
-        let result = String::from_utf8(buf).unwrap();
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(tag = "type")]
+        enum Internal {
+            #[serde(alias = "a")]
+            A { x: u8 },
+            #[serde(other)]
+            Unknown,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Test {
+            #[serde(alias = "ext")]
+            external: External,
+            internal: Internal,
+        }
 
         assert_eq_str!(
-            result,
-            r#"export type Usize = number;
-export type Test = {
-    "a": Usize;
-};
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U8 = number;
+    export type External = ("A" | {
+        "B": types.U8;
+    } | "Unknown");
+    export type Internal = (({
+        "type": "A";
+    } & {
+        "x": types.U8;
+    }) | {
+        "type": "Unknown";
+    });
+    export type Test = {
+        "external": types.External;
+        "internal": types.Internal;
+    };
+    export type ExternalInput = ("A" | 
+    /**
+     * An alias of `A`.
+     */
+"a" | 
+    /**
+     * An alias of `A`.
+     */
+"first" | {
+        "B": types.U8;
+    } | "Unknown" | (string & {
+    }));
+    export type InternalInput = (({
+        "type": "A";
+    } & {
+        "x": types.U8;
+    }) | ({
+
+        /**
+         * An alias of `A`.
+         */
+        "type": "a";
+    } & {
+        "x": types.U8;
+    }) | {
+        "type": "Unknown";
+    } | {
+
+        /**
+         * Any other tag.
+         */
+        "type": (string & {
+        });
+    });
+    export type TestInput = {
+        "external"?: types.ExternalInput;
+
+        /**
+         * An alias of `external`.
+         */
+        "ext"?: types.ExternalInput;
+        "internal": types.InternalInput;
+    };
+}
 "#
         );
     }
-}
 
-#[cfg(feature = "json_value")]
-mod json_value {
-    use super::test_emit;
-    use serde::Serialize;
-    use typescript_type_def::TypeDef;
     #[test]
-    fn json_value() {
-        #[derive(Serialize, TypeDef)]
+    fn transparent() {
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Inner {
+            a: u8,
+        }
+
+        /// A wrapper.
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(transparent)]
+        struct Wrapper {
+            inner: Inner,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(transparent)]
+        struct Generic<T> {
+            value: Vec<T>,
+            #[serde(skip)]
+            cache: Option<String>,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(transparent)]
+        struct Foreign {
+            #[type_def(type_of = "String")]
+            value: std::path::PathBuf,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(transparent)]
+        #[type_def(brand)]
+        struct Id {
+            id: u64,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
         struct Test {
-            a: String,
-            b: serde_json::Value,
-            c: serde_json::Number,
+            a: Wrapper,
+            b: Generic<u8>,
+            c: Foreign,
+            d: Id,
         }
 
+        assert_eq_str!(
+            serde_json::to_string(&Wrapper {
+                inner: Inner { a: 1 }
+            })
+            .unwrap(),
+            r#"{"a":1}"#
+        );
         assert_eq_str!(
             test_emit::<Test>(),
             r#"export default types;
 export namespace types {
-    export type JSONValue = (null | boolean | number | string | (JSONValue)[] | {
-        [key:string]:JSONValue;
-    });
+    export type U8 = number;
+    export type Inner = {
+        "a": types.U8;
+    };
+
+    /**
+     * A wrapper.
+     */
+    export type Wrapper = types.Inner;
+    export type Generic<T> = (T)[];
+    export type Foreign = string;
+    export type U64 = number;
+    export type Id = (types.U64 & { readonly __brand: "Id" });
+    export const Id = (value: types.U64): Id => value as Id;
     export type Test = {
-        "a": string;
-        "b": types.JSONValue;
-        "c": number;
+        "a": types.Wrapper;
+        "b": types.Generic<types.U8>;
+        "c": types.Foreign;
+        "d": types.Id;
     };
 }
 "#
         );
     }
-}
-
-mod write_ref_expr {
-    use super::*;
 
     #[test]
-    fn root_namespace() {
-        #[derive(Serialize, TypeDef)]
-        struct Test {
-            a: usize,
+    fn proxy() {
+        use std::convert::TryFrom;
+
+        /// An email address.
+        #[derive(Clone, Serialize, Deserialize, TypeDef)]
+        #[serde(try_from = "String", into = "String")]
+        struct Email {
+            user: String,
+            domain: String,
         }
 
-        let mut buf = Vec::new();
-        Test::INFO.write_ref_expr(&mut buf, Some("types")).unwrap();
-        let result = String::from_utf8(buf).unwrap();
+        impl TryFrom<String> for Email {
+            type Error = &'static str;
 
-        assert_eq_str!(result, r#"types.Test"#);
-    }
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                let (user, domain) =
+                    value.split_once('@').ok_or("missing `@`")?;
+                Ok(Self {
+                    user: user.to_owned(),
+                    domain: domain.to_owned(),
+                })
+            }
+        }
 
-    #[test]
-    fn no_root_namespace() {
-        #[derive(Serialize, TypeDef)]
+        impl From<Email> for String {
+            fn from(email: Email) -> Self {
+                format!("{}@{}", email.user, email.domain)
+            }
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Point {
+            x: u8,
+            y: u8,
+        }
+
+        #[derive(Deserialize, TypeDef)]
+        #[serde(from = "Point")]
+        enum Quadrant {
+            Positive,
+            Origin,
+        }
+
+        impl From<Point> for Quadrant {
+            fn from(point: Point) -> Self {
+                if point.x == 0 && point.y == 0 {
+                    Self::Origin
+                } else {
+                    Self::Positive
+                }
+            }
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
         struct Test {
-            a: usize,
+            email: Email,
+            #[serde(skip_serializing)]
+            quadrant: Option<Quadrant>,
         }
 
-        let mut buf = Vec::new();
-        Test::INFO.write_ref_expr(&mut buf, None).unwrap();
-        let result = String::from_utf8(buf).unwrap();
+        assert_eq_str!(
+            serde_json::to_string(&Email {
+                user: "a".to_owned(),
+                domain: "b.c".to_owned()
+            })
+            .unwrap(),
+            r#""a@b.c""#
+        );
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
 
-        assert_eq_str!(result, r#"Test"#);
+    /**
+     * An email address.
+     */
+    export type Email = string;
+    export type Test = {
+        "email": types.Email;
+    };
+    export type U8 = number;
+    export type Point = {
+        "x": types.U8;
+        "y": types.U8;
+    };
+    export type Quadrant = types.Point;
+    export type TestInput = {
+        "email": types.Email;
+        "quadrant"?: (types.Quadrant | null);
+    };
+}
+"#
+        );
     }
 
     #[test]
-    fn generics() {
-        #[derive(Serialize, TypeDef)]
-        struct Test<T> {
-            a: T,
+    fn remote() {
+        mod foreign {
+            pub struct Point {
+                pub x: i32,
+                pub y: i32,
+            }
+
+            #[derive(serde::Serialize, serde::Deserialize)]
+            pub struct Id(pub u64);
         }
 
-        let mut buf = Vec::new();
-        Test::<Vec<u8>>::INFO
-            .write_ref_expr(&mut buf, Some("types"))
-            .unwrap();
-        let result = String::from_utf8(buf).unwrap();
+        /// A point.
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(remote = "foreign::Point")]
+        struct PointDef {
+            x: i32,
+            y: i32,
+        }
 
-        assert_eq_str!(result, r#"types.Test<(types.U8)[]>"#);
+        #[allow(dead_code)]
+        #[derive(TypeDef)]
+        #[type_def(remote = "foreign::Id", namespace = "foreign")]
+        struct IdDef(u64);
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Test {
+            #[serde(with = "PointDef")]
+            #[type_def(with = "PointDef")]
+            point: foreign::Point,
+            #[type_def(with = "IdDef")]
+            id: foreign::Id,
+        }
+
+        assert_eq_str!(
+            serde_json::to_string(&Test {
+                point: foreign::Point { x: 1, y: 2 },
+                id: foreign::Id(3),
+            })
+            .unwrap(),
+            r#"{"point":{"x":1,"y":2},"id":3}"#
+        );
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type I32 = number;
+
+    /**
+     * A point.
+     */
+    export type Point = {
+        "x": types.I32;
+        "y": types.I32;
+    };
+    export type U64 = number;
+    export namespace foreign {
+        export type Id = types.U64;
+    }
+    export type Test = {
+        "point": types.Point;
+        "id": types.foreign.Id;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn struct_tag() {
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(tag = "type")]
+        struct Ping {
+            id: u8,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(tag = "kind", rename = "pong", deny_unknown_fields)]
+        struct Pong {}
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Test {
+            ping: Ping,
+            pong: Pong,
+        }
+
+        assert_eq_str!(
+            serde_json::to_string(&Test {
+                ping: Ping { id: 1 },
+                pong: Pong {},
+            })
+            .unwrap(),
+            r#"{"ping":{"type":"Ping","id":1},"pong":{"kind":"pong"}}"#
+        );
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U8 = number;
+    export type Ping = ({
+        "type": "Ping";
+    } & {
+        "id": types.U8;
+    });
+    export type pong = ({
+        "kind": "pong";
+    } & {
+    });
+    export type Test = {
+        "ping": types.Ping;
+        "pong": types.pong;
+    };
+    export type PingInput = ({
+        "type"?: "Ping";
+    } & {
+        "id": types.U8;
+    });
+    export type pongInput = {
+    };
+    export type TestInput = {
+        "ping": types.PingInput;
+        "pong": types.pongInput;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn split_rename() {
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(
+            rename_all(serialize = "UPPERCASE"),
+            rename_all_fields = "camelCase"
+        )]
+        enum Event {
+            #[serde(rename(serialize = "created", deserialize = "new"))]
+            Created { user_id: u8 },
+            #[serde(rename_all = "kebab-case")]
+            Deleted { user_id: u8 },
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(rename(deserialize = "Request"))]
+        #[serde(rename_all(deserialize = "SCREAMING_SNAKE_CASE"))]
+        struct Test {
+            #[serde(rename(serialize = "evt"))]
+            event: Event,
+            retry_count: u8,
+        }
+
+        assert_eq_str!(
+            serde_json::to_string(&Test {
+                event: Event::Deleted { user_id: 1 },
+                retry_count: 2,
+            })
+            .unwrap(),
+            r#"{"evt":{"DELETED":{"user-id":1}},"retry_count":2}"#
+        );
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U8 = number;
+    export type Event = ({
+        "created": {
+            "userId": types.U8;
+        };
+    } | {
+        "DELETED": {
+            "user-id": types.U8;
+        };
+    });
+    export type Test = {
+        "evt": types.Event;
+        "retry_count": types.U8;
+    };
+    export type EventInput = ({
+        "new": {
+            "userId": types.U8;
+        };
+    } | {
+        "Deleted": {
+            "user-id": types.U8;
+        };
+    });
+    export type Request = {
+        "EVENT": types.EventInput;
+        "RETRY_COUNT": types.U8;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn container_default() {
+        #[derive(Serialize, Deserialize, TypeDef)]
+        #[serde(default)]
+        struct Test {
+            a: String,
+            b: Vec<u8>,
+        }
+
+        impl Default for Test {
+            fn default() -> Self {
+                Self {
+                    a: "a".to_owned(),
+                    b: Vec::new(),
+                }
+            }
+        }
+
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U8 = number;
+    export type Test = {
+        "a": string;
+        "b": (types.U8)[];
+    };
+    export type TestInput = {
+        "a"?: string;
+        "b"?: (types.U8)[];
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn enum_values() {
+        #[derive(Serialize, TypeDef)]
+        #[serde(rename_all = "snake_case")]
+        #[type_def(const_object)]
+        enum Color {
+            /// The color red.
+            DarkRed,
+            Green,
+            #[serde(skip)]
+            Blue,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x", ts_enum)]
+        enum Shape {
+            Circle,
+            #[serde(rename = "square")]
+            Square,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            color: Color,
+            shape: Shape,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type Color = (
+    /**
+     * The color red.
+     */
+"dark_red" | "green");
+    export const Color = {
+
+        /**
+         * The color red.
+         */
+        DarkRed: "dark_red",
+        Green: "green",
+    } as const;
+    export const ColorValues = [Color.DarkRed, Color.Green] as const;
+    export namespace x {
+        export enum Shape {
+            Circle = "Circle",
+            Square = "square",
+        }
+        export const ShapeValues = [Shape.Circle, Shape.Square] as const;
+    }
+    export type Test = {
+        "color": types.Color;
+        "shape": types.x.Shape;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn foreign_field() {
+        #[derive(Serialize)]
+        #[serde(transparent)]
+        struct ExternalString(String);
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(transparent)]
+        struct ExternalStringWrapper(
+            #[type_def(type_of = "String")] ExternalString,
+        );
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            #[type_def(type_of = "String")]
+            a: ExternalString,
+            b: ExternalStringWrapper,
+            c: String,
+            #[type_def(type_of = "i16")]
+            d: usize,
+            #[type_def(flatten, type_of = "Test3")]
+            e: Test2,
+        }
+
+        #[derive(Serialize)]
+        struct Test2 {
+            foo: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test3 {
+            bar: String,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type Test3 = {
+        "bar": string;
+    };
+    export type ExternalStringWrapper = string;
+    export type I16 = number;
+    export type Test = (types.Test3 & {
+        "a": string;
+        "b": types.ExternalStringWrapper;
+        "c": string;
+        "d": types.I16;
+    });
+}
+"#
+        );
+    }
+
+    #[test]
+    fn no_root_namespace() {
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: usize,
+        }
+
+        let mut buf = Vec::new();
+        let options = DefinitionFileOptions {
+            header: None,
+            root_namespace: None,
+            module_style: ModuleStyle::Namespaces,
+            name_conflicts: NameConflicts::Error,
+            input_suffix: "Input",
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+            brand_numbers: false,
+        };
+        write_definition_file::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"export type Usize = number;
+export type Test = {
+    "a": Usize;
+};
+"#
+        );
+    }
+
+    #[test]
+    fn flat_module_style() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x.y")]
+        struct Inner {
+            a: usize,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: Inner,
+        }
+
+        let mut buf = Vec::new();
+        let options = DefinitionFileOptions {
+            header: None,
+            root_namespace: Some("types"),
+            module_style: ModuleStyle::Flat,
+            name_conflicts: NameConflicts::Error,
+            input_suffix: "Input",
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+            brand_numbers: false,
+        };
+        write_definition_file::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"export type Usize = number;
+export type x_y_Inner = {
+    "a": Usize;
+};
+export type Test = {
+    "a": x_y_Inner;
+};
+"#
+        );
+    }
+
+    #[test]
+    fn int_repr() {
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: u64,
+            b: i128,
+            #[type_def(int_repr = "string")]
+            c: u64,
+            #[type_def(int_repr = "number")]
+            d: Option<u128>,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U64 = number;
+    export type I128 = string;
+    export type Test = {
+        "a": types.U64;
+        "b": types.I128;
+        "c": string;
+        "d": (number | null);
+    };
+}
+"#
+        );
+
+        let mut buf = Vec::new();
+        let options = DefinitionFileOptions {
+            int64_repr: IntegerRepr::BigInt,
+            int128_repr: IntegerRepr::BigInt,
+            ..TEST_OPTIONS
+        };
+        write_definition_file::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"export default types;
+export namespace types {
+    export type U64 = bigint;
+    export type I128 = bigint;
+    export type Test = {
+        "a": types.U64;
+        "b": types.I128;
+        "c": string;
+        "d": (number | null);
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn brand() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(brand)]
+        struct UserId(u64);
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(brand, namespace = "org")]
+        struct OrgId(u64);
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(brand)]
+        struct Wrapper<T>(T);
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: UserId,
+            b: OrgId,
+            c: Wrapper<u8>,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U64 = number;
+    export type UserId = (types.U64 & { readonly __brand: "UserId" });
+    export const UserId = (value: types.U64): UserId => value as UserId;
+    export namespace org {
+        export type OrgId = (types.U64 & { readonly __brand: "org.OrgId" });
+        export const OrgId = (value: types.U64): OrgId => value as OrgId;
+    }
+    export type U8 = number;
+    export type Wrapper<T> = (T & { readonly __brand: "Wrapper" });
+    export const Wrapper = <T>(value: T): Wrapper<T> => value as Wrapper<T>;
+    export type Test = {
+        "a": types.UserId;
+        "b": types.org.OrgId;
+        "c": types.Wrapper<types.U8>;
+    };
+}
+"#
+        );
+
+        let mut buf = Vec::new();
+        let options = DefinitionFileOptions {
+            brand_numbers: true,
+            ..TEST_OPTIONS
+        };
+        write_definition_file::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"export default types;
+export namespace types {
+    export type U64 = (number & { readonly __brand: "U64" });
+    export const U64 = (value: number): U64 => value as U64;
+    export type UserId = (types.U64 & { readonly __brand: "UserId" });
+    export const UserId = (value: types.U64): UserId => value as UserId;
+    export namespace org {
+        export type OrgId = (types.U64 & { readonly __brand: "org.OrgId" });
+        export const OrgId = (value: types.U64): OrgId => value as OrgId;
+    }
+    export type U8 = (number & { readonly __brand: "U8" });
+    export const U8 = (value: number): U8 => value as U8;
+    export type Wrapper<T> = (T & { readonly __brand: "Wrapper" });
+    export const Wrapper = <T>(value: T): Wrapper<T> => value as Wrapper<T>;
+    export type Test = {
+        "a": types.UserId;
+        "b": types.org.OrgId;
+        "c": types.Wrapper<types.U8>;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn raw_ts() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(ts = "`${number}px`")]
+        struct Pixels(String);
+
+        #[derive(Serialize, TypeDef)]
+        struct Inner {
+            a: Vec<u8>,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(ts = "Partial<types.Inner>", ts_deps = "Inner")]
+        struct PartialInner(HashMap<String, Vec<u8>>);
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            #[type_def(ts = "Record<string, unknown>")]
+            a: HashMap<String, String>,
+            #[type_def(ts = "types.Inner | \"none\"", ts_deps = "Inner, u32")]
+            b: Option<Inner>,
+            c: Pixels,
+            d: PartialInner,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U8 = number;
+    export type Inner = {
+        "a": (types.U8)[];
+    };
+    export type U32 = number;
+    export type Pixels = (`${number}px`);
+    export type PartialInner = (Partial<types.Inner>);
+    export type Test = {
+        "a": (Record<string, unknown>);
+        "b": (types.Inner | "none");
+        "c": types.Pixels;
+        "d": types.PartialInner;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn std_types() {
+        use std::{
+            cell::{Cell, RefCell},
+            cmp::Reverse,
+            collections::{BinaryHeap, VecDeque},
+            num::Wrapping,
+            rc::{Rc, Weak},
+            sync::{atomic::AtomicU32, Arc, Mutex},
+        };
+
+        // serde only implements `Serialize` for `Rc` and `Arc` with its `rc`
+        // feature enabled
+        #[allow(dead_code)]
+        #[derive(TypeDef)]
+        struct Test {
+            a: Rc<str>,
+            b: Arc<[bool]>,
+            c: Cell<u8>,
+            d: RefCell<Vec<String>>,
+            e: Mutex<bool>,
+            f: VecDeque<String>,
+            g: BinaryHeap<Reverse<u8>>,
+            h: Wrapping<u8>,
+            i: AtomicU32,
+            j: Weak<String>,
+            k: PhantomData<String>,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U8 = number;
+    export type U32 = number;
+    export type Test = {
+        "a": string;
+        "b": (boolean)[];
+        "c": types.U8;
+        "d": (string)[];
+        "e": boolean;
+        "f": (string)[];
+        "g": (types.U8)[];
+        "h": types.U8;
+        "i": types.U32;
+        "j": (string | null);
+        "k": null;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn std_named_types() {
+        use std::{
+            net::{Ipv4Addr, SocketAddr},
+            ops::{Bound, Range, RangeInclusive},
+            time::{Duration, SystemTime},
+        };
+
+        // doesn't conflict with `std::time::Duration`
+        #[derive(Serialize, TypeDef)]
+        #[type_def(rename = "Duration")]
+        struct MyDuration(String);
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: Duration,
+            b: Option<Duration>,
+            c: SystemTime,
+            d: Range<u8>,
+            e: RangeInclusive<String>,
+            f: Bound<u8>,
+            g: Ipv4Addr,
+            h: SocketAddr,
+            i: MyDuration,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type U64 = number;
+    export type U32 = number;
+    export namespace std {
+
+        /**
+         * A duration as whole seconds and nanoseconds, e.g. `{ "secs": 90, "nanos": 500000000 }` for 90.5 seconds.
+         */
+        export type Duration = {
+            "secs": types.U64;
+            "nanos": types.U32;
+        };
+    }
+    export namespace std {
+
+        /**
+         * A point in time as whole seconds and nanoseconds since the Unix epoch, e.g. `{ "secs_since_epoch": 1704164645, "nanos_since_epoch": 0 }`.
+         */
+        export type SystemTime = {
+            "secs_since_epoch": types.U64;
+            "nanos_since_epoch": types.U32;
+        };
+    }
+    export type U8 = number;
+    export namespace std {
+
+        /**
+         * A half-open range containing `start` but not `end`.
+         */
+        export type Range<Idx> = {
+            "start": Idx;
+            "end": Idx;
+        };
+    }
+    export namespace std {
+
+        /**
+         * A closed range containing both `start` and `end`.
+         */
+        export type RangeInclusive<Idx> = {
+            "start": Idx;
+            "end": Idx;
+        };
+    }
+    export namespace std {
+
+        /**
+         * An endpoint of a range, e.g. `{ "Included": 1 }`, `{ "Excluded": 1 }` or `"Unbounded"`.
+         */
+        export type Bound<T> = ("Unbounded" | {
+            "Included": T;
+        } | {
+            "Excluded": T;
+        });
+    }
+    export namespace std {
+
+        /**
+         * An IPv4 address, e.g. `"127.0.0.1"`.
+         */
+        export type Ipv4Addr = string;
+    }
+    export namespace std {
+
+        /**
+         * An IPv4 or IPv6 socket address, e.g. `"127.0.0.1:8080"` or `"[::1]:8080"`.
+         */
+        export type SocketAddr = string;
+    }
+    export type Duration = string;
+    export type Test = {
+        "a": types.std.Duration;
+        "b": (types.std.Duration | null);
+        "c": types.std.SystemTime;
+        "d": types.std.Range<types.U8>;
+        "e": types.std.RangeInclusive<string>;
+        "f": types.std.Bound<types.U8>;
+        "g": types.std.Ipv4Addr;
+        "h": types.std.SocketAddr;
+        "i": types.Duration;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn string_literals() {
+        #[derive(Serialize, TypeDef)]
+        #[serde(tag = "type\"")]
+        enum Test {
+            #[serde(rename = "a\\b\n\u{0}1\u{2028}")]
+            A,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type Test = {
+        "type\"": "a\\b\n\u00001\u2028";
+    };
+}
+"#
+        );
+    }
+}
+
+mod name_conflicts {
+    use super::*;
+    use typescript_type_def::NameConflictError;
+
+    mod a {
+        use super::*;
+
+        #[derive(Serialize, TypeDef)]
+        pub struct Config {
+            pub a: String,
+        }
+    }
+
+    mod b {
+        use super::*;
+
+        #[derive(Serialize, TypeDef)]
+        pub struct Config {
+            pub b: Option<String>,
+        }
+    }
+
+    #[derive(Serialize, TypeDef)]
+    struct Config2;
+
+    #[derive(Serialize, TypeDef)]
+    struct Test {
+        a: a::Config,
+        b: b::Config,
+        c: Config2,
+        d: a::Config,
+    }
+
+    #[test]
+    fn error() {
+        let mut buf = Vec::new();
+        let err = write_definition_file::<_, Test>(&mut buf, TEST_OPTIONS)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        let err = err
+            .get_ref()
+            .unwrap()
+            .downcast_ref::<NameConflictError>()
+            .unwrap();
+        assert_eq!(err.first.name.0, "Config");
+        assert_eq!(err.second.name.0, "Config");
+        assert_eq_str!(
+            err.to_string(),
+            r#"conflicting type definitions for `Config`: `export type Config = {
+    "a": string;
+};` and `export type Config = {
+    "b": (string | null);
+};`"#
+        );
+    }
+
+    #[test]
+    fn suffix() {
+        let mut buf = Vec::new();
+        let options = DefinitionFileOptions {
+            name_conflicts: NameConflicts::Suffix,
+            ..TEST_OPTIONS
+        };
+        let stats =
+            write_definition_file::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq!(stats.type_definitions, 4);
+        assert_eq_str!(
+            result,
+            r#"export default types;
+export namespace types {
+    export type Config = {
+        "a": string;
+    };
+    export type Config3 = {
+        "b": (string | null);
+    };
+    export type Config2 = null;
+    export type Test = {
+        "a": types.Config;
+        "b": types.Config3;
+        "c": types.Config2;
+        "d": types.Config;
+    };
+}
+"#
+        );
+    }
+
+    #[test]
+    fn flat_module_style() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "a.b_c")]
+        struct D {
+            a: String,
+        }
+
+        mod other {
+            use super::*;
+
+            #[derive(Serialize, TypeDef)]
+            #[type_def(namespace = "a_b.c")]
+            pub struct D {
+                pub b: bool,
+            }
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: D,
+            b: other::D,
+        }
+
+        let options = DefinitionFileOptions {
+            root_namespace: None,
+            module_style: ModuleStyle::Flat,
+            ..TEST_OPTIONS
+        };
+        let mut buf = Vec::new();
+        let err =
+            write_definition_file::<_, Test>(&mut buf, options).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let mut buf = Vec::new();
+        let options = DefinitionFileOptions {
+            name_conflicts: NameConflicts::Suffix,
+            ..options
+        };
+        write_definition_file::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"export type a_b_c_D = {
+    "a": string;
+};
+export type a_b_c_D2 = {
+    "b": boolean;
+};
+export type Test = {
+    "a": a_b_c_D;
+    "b": a_b_c_D2;
+};
+"#
+        );
+    }
+}
+
+#[cfg(feature = "json_value")]
+mod json_value {
+    use super::test_emit;
+    use serde::Serialize;
+    use typescript_type_def::TypeDef;
+    #[test]
+    fn json_value() {
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: String,
+            b: serde_json::Value,
+            c: serde_json::Number,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type JSONValue = (null | boolean | number | string | (JSONValue)[] | {
+        [key:string]:JSONValue;
+    });
+    export type Test = {
+        "a": string;
+        "b": types.JSONValue;
+        "c": number;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn chrono() {
+        #[derive(TypeDef)]
+        struct Test {
+            a: chrono::DateTime<chrono::Utc>,
+            b: chrono::NaiveDate,
+            c: chrono::TimeDelta,
+            d: chrono::Weekday,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export namespace chrono {
+
+        /**
+         * An RFC 3339 date and time with a UTC offset, e.g. `"2024-01-02T03:04:05.678+09:00"` or `"2024-01-02T03:04:05Z"`.
+         */
+        export type DateTime = string;
+    }
+    export namespace chrono {
+
+        /**
+         * An ISO 8601 calendar date, e.g. `"2024-01-02"`.
+         */
+        export type NaiveDate = string;
+    }
+    export type I64 = number;
+    export type I32 = number;
+    export namespace chrono {
+
+        /**
+         * A duration as a tuple of whole seconds and nanoseconds, e.g. `[90, 500000000]` for 90.5 seconds.
+         */
+        export type TimeDelta = [types.I64, types.I32];
+    }
+    export namespace chrono {
+
+        /**
+         * A day of the week.
+         */
+        export type Weekday = ("Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun");
+    }
+    export type Test = {
+        "a": types.chrono.DateTime;
+        "b": types.chrono.NaiveDate;
+        "c": types.chrono.TimeDelta;
+        "d": types.chrono.Weekday;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn time() {
+        #[derive(TypeDef)]
+        struct Test {
+            a: time::OffsetDateTime,
+            b: time::Duration,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export namespace time {
+
+        /**
+         * A date and time with a UTC offset, e.g. `"2024-01-02 03:04:05.678 +09:00:00"`.
+         */
+        export type OffsetDateTime = string;
+    }
+    export namespace time {
+
+        /**
+         * A duration in seconds with nanosecond precision, e.g. `"90.500000000"` or `"-1.000000000"`.
+         */
+        export type Duration = string;
+    }
+    export type Test = {
+        "a": types.time.OffsetDateTime;
+        "b": types.time.Duration;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(all(feature = "chrono", feature = "time"))]
+mod date_time_namespaces {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn date_time_namespaces() {
+        #[derive(TypeDef)]
+        struct Test {
+            a: chrono::Weekday,
+            b: time::Weekday,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export namespace chrono {
+
+        /**
+         * A day of the week.
+         */
+        export type Weekday = ("Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun");
+    }
+    export namespace time {
+
+        /**
+         * A day of the week.
+         */
+        export type Weekday = ("Monday" | "Tuesday" | "Wednesday" | "Thursday" | "Friday" | "Saturday" | "Sunday");
+    }
+    export type Test = {
+        "a": types.chrono.Weekday;
+        "b": types.time.Weekday;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn uuid() {
+        #[derive(TypeDef)]
+        struct Test {
+            id: uuid::Uuid,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+
+    /**
+     * A UUID as a hyphenated hexadecimal string, e.g. `"67e55044-10b1-426f-9247-bb680e5fe0c8"`.
+     */
+    export type Uuid = string;
+    export type Test = {
+        "id": types.Uuid;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(feature = "ulid")]
+mod ulid {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn ulid() {
+        #[derive(TypeDef)]
+        struct Test {
+            id: ulid::Ulid,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+
+    /**
+     * A ULID as a 26-character Crockford base32 string, e.g. `"01ARZ3NDEKTSV4RRFFQ69G5FAV"`.
+     */
+    export type Ulid = string;
+    export type Test = {
+        "id": types.Ulid;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(feature = "bson")]
+mod bson {
+    #![allow(dead_code)]
+
+    use super::test_emit_with_inputs;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn bson() {
+        #[derive(TypeDef)]
+        struct Test {
+            #[serde(rename = "_id")]
+            id: bson::oid::ObjectId,
+        }
+
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+
+    /**
+     * A BSON ObjectId in MongoDB Extended JSON format, e.g. `{ "$oid": "507f1f77bcf86cd799439011" }`.
+     */
+    export type ObjectId = {
+        "$oid": string;
+    };
+    export type Test = {
+        "_id": types.ObjectId;
+    };
+
+    /**
+     * A BSON ObjectId as a 24-character hexadecimal string or in MongoDB Extended JSON format, e.g. `"507f1f77bcf86cd799439011"` or `{ "$oid": "507f1f77bcf86cd799439011" }`.
+     */
+    export type ObjectIdInput = (string | types.ObjectId);
+    export type TestInput = {
+        "_id": types.ObjectIdInput;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[derive(TypeDef)]
+    struct Test {
+        price: rust_decimal::Decimal,
+    }
+
+    #[cfg(not(feature = "rust_decimal_float"))]
+    #[test]
+    fn rust_decimal() {
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+
+    /**
+     * A decimal number as a string, e.g. `"3.14"`.
+     */
+    export type Decimal = string;
+    export type Test = {
+        "price": types.Decimal;
+    };
+}
+"#
+        );
+    }
+
+    #[cfg(feature = "rust_decimal_float")]
+    #[test]
+    fn rust_decimal_float() {
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+
+    /**
+     * A decimal number, e.g. `3.14`.
+     */
+    export type Decimal = number;
+    export type Test = {
+        "price": types.Decimal;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(all(feature = "num-bigint", feature = "ordered-float"))]
+mod big_numbers {
+    #![allow(dead_code)]
+
+    use super::test_emit;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn big_numbers() {
+        #[derive(TypeDef)]
+        struct Test {
+            a: num_bigint::BigInt,
+            b: ordered_float::OrderedFloat<f64>,
+        }
+
+        assert_eq_str!(
+            test_emit::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type I8 = number;
+    export type U32 = number;
+
+    /**
+     * An arbitrary-precision unsigned integer as its base 2^32 digits in little-endian order, e.g. `[1, 2]` for 8589934593.
+     */
+    export type BigUint = (types.U32)[];
+
+    /**
+     * An arbitrary-precision integer as a tuple of its sign (`-1`, `0` or `1`) and magnitude, e.g. `[-1, [1, 2]]` for -8589934593.
+     */
+    export type BigInt = [types.I8, types.BigUint];
+    export type F64 = number;
+    export type Test = {
+        "a": types.BigInt;
+        "b": types.F64;
+    };
+}
+"#
+        );
+    }
+}
+
+#[cfg(all(
+    feature = "indexmap",
+    feature = "smallvec",
+    feature = "arrayvec",
+    feature = "bytes",
+    feature = "heapless",
+))]
+mod collections {
+    #![allow(dead_code)]
+
+    use super::test_emit_with_inputs;
+    use typescript_type_def::TypeDef;
+
+    #[test]
+    fn collections() {
+        #[derive(TypeDef)]
+        struct Test {
+            a: indexmap::IndexMap<String, usize>,
+            b: indexmap::IndexSet<String>,
+            c: smallvec::SmallVec<[bool; 4]>,
+            d: arrayvec::ArrayVec<bool, 4>,
+            e: arrayvec::ArrayString<16>,
+            f: bytes::Bytes,
+            g: heapless::Vec<String, 4>,
+            h: heapless::String<16>,
+        }
+
+        assert_eq_str!(
+            test_emit_with_inputs::<Test>(),
+            r#"export default types;
+export namespace types {
+    export type Usize = number;
+    export type U8 = number;
+    export type Test = {
+        "a": Record<string, types.Usize>;
+        "b": (string)[];
+        "c": (boolean)[];
+        "d": (boolean)[];
+        "e": string;
+        "f": (types.U8)[];
+        "g": (string)[];
+        "h": string;
+    };
+    export type TestInput = {
+        "a": Record<string, types.Usize>;
+        "b": (string)[];
+        "c": (boolean)[];
+        "d": (boolean)[];
+        "e": string;
+        "f": ((types.U8)[] | string);
+        "g": (string)[];
+        "h": string;
+    };
+}
+"#
+        );
+    }
+}
+
+mod write_ref_expr {
+    use super::*;
+
+    #[test]
+    fn root_namespace() {
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: usize,
+        }
+
+        let mut buf = Vec::new();
+        Test::INFO.write_ref_expr(&mut buf, Some("types")).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(result, r#"types.Test"#);
+    }
+
+    #[test]
+    fn no_root_namespace() {
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: usize,
+        }
+
+        let mut buf = Vec::new();
+        Test::INFO.write_ref_expr(&mut buf, None).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(result, r#"Test"#);
+    }
+
+    #[test]
+    fn generics() {
+        #[derive(Serialize, TypeDef)]
+        struct Test<T> {
+            a: T,
+        }
+
+        let mut buf = Vec::new();
+        Test::<Vec<u8>>::INFO
+            .write_ref_expr(&mut buf, Some("types"))
+            .unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(result, r#"types.Test<(types.U8)[]>"#);
+    }
+
+    #[test]
+    fn renamed() {
+        mod a {
+            #[derive(serde::Serialize, typescript_type_def::TypeDef)]
+            pub struct Config {
+                pub a: String,
+            }
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Config {
+            #[serde(default)]
+            b: String,
+        }
+
+        #[derive(Serialize, Deserialize, TypeDef)]
+        struct Test {
+            #[serde(skip_deserializing)]
+            a: Option<a::Config>,
+            b: Config,
+        }
+
+        let options = DefinitionFileOptions {
+            name_conflicts: NameConflicts::Suffix,
+            ..TEST_OPTIONS
+        };
+        let mut buf = Vec::new();
+        for info in [&a::Config::INFO, &Config::INFO, &Config::INFO_DE] {
+            info.write_ref_expr_with_options(
+                &mut buf,
+                Some("types"),
+                options,
+                &[&Test::INFO],
+                &[&Test::INFO_DE],
+            )
+            .unwrap();
+            buf.push(b'\n');
+        }
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"types.Config
+types.Config2
+types.ConfigInput
+"#
+        );
+    }
+}
+
+mod write_definition_modules {
+    #![allow(dead_code)]
+
+    use super::*;
+    use std::collections::BTreeMap;
+    use typescript_type_def::{
+        write_definition_modules, DefinitionModulesOptions,
+    };
+
+    #[test]
+    fn modules() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x")]
+        struct Test {
+            a: usize,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x.y")]
+        struct Inner {
+            a: Test,
+            b: Outer,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Outer {
+            a: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(rename = "Test")]
+        struct Root {
+            a: Inner,
+            b: Test,
+            c: Outer,
+        }
+
+        let mut modules = BTreeMap::new();
+        let options = DefinitionModulesOptions {
+            header: None,
+            root_module: "index",
+            name_conflicts: NameConflicts::Error,
+            int64_repr: IntegerRepr::Number,
+            int128_repr: IntegerRepr::String,
+            brand_numbers: false,
+        };
+        let stats =
+            write_definition_modules::<_, Root>(&mut modules, options).unwrap();
+        let mut modules = modules
+            .into_iter()
+            .map(|(path, contents)| {
+                (path, String::from_utf8(contents).unwrap())
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(stats.type_definitions, 5);
+        assert_eq!(
+            modules.keys().collect::<Vec<_>>(),
+            ["index.ts", "x.ts", "x/y.ts"]
+        );
+        assert_eq_str!(
+            modules.remove("index.ts").unwrap(),
+            r#"import type { Test as x_Test } from "./x";
+import type { Inner } from "./x/y";
+
+export type Usize = number;
+export type Outer = {
+    "a": string;
+};
+export type Test = {
+    "a": Inner;
+    "b": x_Test;
+    "c": Outer;
+};
+"#
+        );
+        assert_eq_str!(
+            modules.remove("x.ts").unwrap(),
+            r#"import type { Usize } from "./index";
+
+export type Test = {
+    "a": Usize;
+};
+"#
+        );
+        assert_eq_str!(
+            modules.remove("x/y.ts").unwrap(),
+            r#"import type { Outer } from "../index";
+import type { Test } from "../x";
+
+export type Inner = {
+    "a": Test;
+    "b": Outer;
+};
+"#
+        );
+    }
+
+    #[test]
+    fn runtime_values() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x", brand)]
+        struct UserId(u64);
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x", const_object)]
+        enum Color {
+            Red,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x")]
+        struct User {
+            name: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: UserId,
+            b: Color,
+            c: User,
+        }
+
+        let mut modules = BTreeMap::new();
+        let options = DefinitionModulesOptions {
+            header: None,
+            ..Default::default()
+        };
+        write_definition_modules::<_, Test>(&mut modules, options).unwrap();
+
+        assert_eq_str!(
+            String::from_utf8(modules.remove("index.ts").unwrap()).unwrap(),
+            r#"import type { User } from "./x";
+import { Color, UserId } from "./x";
+
+export type U64 = number;
+export type Test = {
+    "a": UserId;
+    "b": Color;
+    "c": User;
+};
+"#
+        );
+    }
+
+    #[test]
+    fn root_module_conflict() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "index")]
+        struct Inner {
+            a: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: Inner,
+        }
+
+        let mut modules = BTreeMap::new();
+        let err = write_definition_modules::<_, Test>(
+            &mut modules,
+            Default::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+mod type_guards {
+    #![allow(dead_code)]
+
+    use super::*;
+    use std::collections::BTreeMap;
+    use typescript_type_def::{write_type_guards, TypeGuardOptions};
+
+    #[test]
+    fn type_guards() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x")]
+        struct Inner<T> {
+            a: T,
+            b: (u8, Option<String>),
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(tag = "type")]
+        enum Test {
+            A {
+                a: Inner<i32>,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                b: Option<Vec<bool>>,
+            },
+            B {
+                map: BTreeMap<String, f64>,
+            },
+        }
+
+        let mut buf = Vec::new();
+        let options = TypeGuardOptions {
+            header: None,
+            check_number_ranges: true,
+            ..Default::default()
+        };
+        let stats = write_type_guards::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq!(stats.type_definitions, 5);
+        assert_eq_str!(
+            result,
+            r#"import type types from "./types";
+
+function __isObject(x: unknown): x is Record<string, unknown> {
+    return typeof x === "object" && x !== null && !Array.isArray(x);
+}
+
+export function isU8(x: unknown): x is types.U8 {
+    return (typeof x === "number" && Number.isInteger(x) && x >= 0 && x <= 255);
+}
+export function isI32(x: unknown): x is types.I32 {
+    return (typeof x === "number" && Number.isInteger(x) && x >= -2147483648 && x <= 2147483647);
+}
+export function isx_Inner<T>(x: unknown, isT: (x: unknown) => x is T): x is types.x.Inner<T> {
+    return (__isObject(x) && isT(x["a"]) && ((x1: unknown): x1 is [types.U8, (string | null)] => (Array.isArray(x1) && x1.length === 2 && isU8(x1[0]) && (typeof x1[1] === "string" || x1[1] === null)))(x["b"]));
+}
+export function isF64(x: unknown): x is types.F64 {
+    return typeof x === "number";
+}
+export function isTest(x: unknown): x is types.Test {
+    return (((__isObject(x) && x["type"] === "A") && (__isObject(x) && isx_Inner(x["a"], isI32) && (x["b"] === undefined || ((x1: unknown): x1 is (boolean)[] => (Array.isArray(x1) && x1.every((x2: unknown): x2 is boolean => typeof x2 === "boolean")))(x["b"])))) || ((__isObject(x) && x["type"] === "B") && (__isObject(x) && ((x1: unknown): x1 is Record<string, types.F64> => (__isObject(x1) && Object.values(x1).every(isF64)))(x["map"]))));
+}
+"#
+        );
+    }
+    #[test]
+    fn deny_unknown_fields() {
+        #[derive(Serialize, TypeDef)]
+        #[serde(deny_unknown_fields)]
+        struct Closed {
+            a: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(tag = "type", deny_unknown_fields)]
+        enum Test {
+            A { closed: Closed },
+            B,
+        }
+
+        let mut buf = Vec::new();
+        let options = TypeGuardOptions {
+            header: None,
+            ..Default::default()
+        };
+        write_type_guards::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import type types from "./types";
+
+function __isObject(x: unknown): x is Record<string, unknown> {
+    return typeof x === "object" && x !== null && !Array.isArray(x);
+}
+
+export function isClosed(x: unknown): x is types.Closed {
+    return (__isObject(x) && typeof x["a"] === "string" && Object.keys(x).every((k) => ["a"].includes(k)));
+}
+export function isTest(x: unknown): x is types.Test {
+    return (((__isObject(x) && x["type"] === "A") && (__isObject(x) && isClosed(x["closed"])) && Object.keys(x).every((k) => ["type", "closed"].includes(k))) || (__isObject(x) && x["type"] === "B"));
+}
+"#
+        );
+    }
+
+    #[test]
+    fn custom_number_alias() {
+        #[derive(Serialize, TypeDef)]
+        struct Test {
+            a: U8,
+            b: u16,
+        }
+
+        /// A user-defined alias with the same name as a `std` integer alias.
+        #[derive(Serialize)]
+        struct U8(f64);
+
+        impl TypeDef for U8 {
+            const INFO: TypeInfo = TypeInfo::Defined(DefinedTypeInfo {
+                def: TypeDefinition {
+                    docs: None,
+                    path: &[],
+                    name: Ident("U8"),
+                    generic_vars: &[],
+                    def: TypeExpr::ident(Ident("number")),
+                    enum_values: None,
+                    brand: false,
+                    std_number: false,
+                },
+                generic_args: &[],
+            });
+        }
+
+        let mut buf = Vec::new();
+        let options = TypeGuardOptions {
+            header: None,
+            check_number_ranges: true,
+            ..Default::default()
+        };
+        write_type_guards::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import type types from "./types";
+
+function __isObject(x: unknown): x is Record<string, unknown> {
+    return typeof x === "object" && x !== null && !Array.isArray(x);
+}
+
+export function isU8(x: unknown): x is types.U8 {
+    return typeof x === "number";
+}
+export function isU16(x: unknown): x is types.U16 {
+    return (typeof x === "number" && Number.isInteger(x) && x >= 0 && x <= 65535);
+}
+export function isTest(x: unknown): x is types.Test {
+    return (__isObject(x) && isU8(x["a"]) && isU16(x["b"]));
+}
+"#
+        );
+    }
+
+    #[test]
+    fn other_variant() {
+        #[derive(Deserialize, TypeDef)]
+        #[serde(tag = "type")]
+        enum Test {
+            A,
+            #[serde(other)]
+            Unknown,
+        }
+
+        struct TestInput;
+
+        impl TypeDef for TestInput {
+            const INFO: TypeInfo = <Test as TypeDef>::INFO_DE;
+        }
+
+        let mut buf = Vec::new();
+        let options = TypeGuardOptions {
+            header: None,
+            ..Default::default()
+        };
+        write_type_guards::<_, TestInput>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import type types from "./types";
+
+function __isObject(x: unknown): x is Record<string, unknown> {
+    return typeof x === "object" && x !== null && !Array.isArray(x);
+}
+
+export function isTest(x: unknown): x is types.Test {
+    return ((__isObject(x) && x["type"] === "A") || (__isObject(x) && x["type"] === "Unknown") || (__isObject(x) && typeof x["type"] === "string"));
+}
+"#
+        );
+    }
+
+    #[test]
+    fn integer_reprs() {
+        #[derive(Serialize, TypeDef)]
+        struct Test(u64, std::num::NonZeroI64, u128, i128);
+
+        let mut buf = Vec::new();
+        let options = TypeGuardOptions {
+            header: None,
+            definition_options: DefinitionFileOptions {
+                int64_repr: IntegerRepr::BigInt,
+                ..Default::default()
+            },
+            check_number_ranges: true,
+            ..Default::default()
+        };
+        write_type_guards::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import type types from "./types";
+
+export function isU64(x: unknown): x is types.U64 {
+    return (typeof x === "bigint" && x >= 0n);
+}
+export function isNonZeroI64(x: unknown): x is types.NonZeroI64 {
+    return (typeof x === "bigint" && x !== 0n);
+}
+export function isU128(x: unknown): x is types.U128 {
+    return (typeof x === "string" && /^[0-9]+$/.test(x));
+}
+export function isI128(x: unknown): x is types.I128 {
+    return (typeof x === "string" && /^-?[0-9]+$/.test(x));
+}
+export function isTest(x: unknown): x is types.Test {
+    return (Array.isArray(x) && x.length === 4 && isU64(x[0]) && isNonZeroI64(x[1]) && isU128(x[2]) && isI128(x[3]));
+}
+"#
+        );
+    }
+}
+
+mod zod {
+    #![allow(dead_code)]
+
+    use super::*;
+    use typescript_type_def::{write_zod_schemas, ZodSchemaOptions};
+
+    #[test]
+    fn zod() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x")]
+        struct Tree<T> {
+            value: T,
+            children: Vec<Tree<T>>,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Common {
+            id: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        struct Flattened {
+            #[serde(flatten)]
+            common: Common,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            name: Option<String>,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(untagged)]
+        enum Test {
+            A(Tree<u8>),
+            B(Flattened),
+            C(HashMap<String, bool>),
+            D,
+        }
+
+        let mut buf = Vec::new();
+        let options = ZodSchemaOptions {
+            header: None,
+            ..Default::default()
+        };
+        let stats = write_zod_schemas::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq!(stats.type_definitions, 5);
+        assert_eq_str!(
+            result,
+            r#"import { z } from "zod";
+
+export type x_Tree<T> = {
+    "value": T;
+    "children": (x_Tree<T>)[];
+};
+export function x_TreeSchema<T>(T: z.ZodType<T>): z.ZodType<x_Tree<T>> {
+    return z.object({ "value": T, "children": z.array(z.lazy(() => x_TreeSchema(T))) });
+}
+export const U8Schema = z.number();
+export type U8 = z.infer<typeof U8Schema>;
+export const CommonSchema = z.object({ "id": z.string() });
+export type Common = z.infer<typeof CommonSchema>;
+export const FlattenedSchema = CommonSchema.and(z.object({ "name": z.string().optional() }));
+export type Flattened = z.infer<typeof FlattenedSchema>;
+export const TestSchema = z.union([x_TreeSchema(U8Schema), FlattenedSchema, z.record(z.string(), z.boolean()), z.null()]);
+export type Test = z.infer<typeof TestSchema>;
+"#
+        );
+    }
+    #[test]
+    fn brand() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(brand)]
+        struct UserId(String);
+
+        #[derive(Serialize, TypeDef)]
+        struct Tree {
+            owner: UserId,
+            children: Vec<Tree>,
+        }
+
+        let mut buf = Vec::new();
+        let options = ZodSchemaOptions {
+            header: None,
+            ..Default::default()
+        };
+        write_zod_schemas::<_, Tree>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import { z } from "zod";
+
+export const UserIdSchema = z.string().brand<"UserId">();
+export type UserId = z.infer<typeof UserIdSchema>;
+export type Tree = {
+    "owner": UserId;
+    "children": (Tree)[];
+};
+export const TreeSchema: z.ZodType<Tree> = z.object({ "owner": UserIdSchema, "children": z.array(z.lazy(() => TreeSchema)) });
+"#
+        );
+    }
+
+    #[test]
+    fn deny_unknown_fields() {
+        #[derive(Serialize, TypeDef)]
+        #[serde(deny_unknown_fields)]
+        struct Closed {
+            a: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(tag = "type", deny_unknown_fields)]
+        enum Test {
+            A { closed: Closed },
+            B,
+        }
+
+        let mut buf = Vec::new();
+        let options = ZodSchemaOptions {
+            header: None,
+            ..Default::default()
+        };
+        write_zod_schemas::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import { z } from "zod";
+
+export const ClosedSchema = z.object({ "a": z.string() }).strict();
+export type Closed = z.infer<typeof ClosedSchema>;
+export const TestSchema = z.discriminatedUnion("type", [z.object({ "type": z.literal("A"), "closed": ClosedSchema }).strict(), z.object({ "type": z.literal("B") })]);
+export type Test = z.infer<typeof TestSchema>;
+"#
+        );
+    }
+
+    #[test]
+    fn other_variant() {
+        #[derive(Deserialize, TypeDef)]
+        #[serde(tag = "type")]
+        enum Test {
+            A,
+            #[serde(other)]
+            Unknown,
+        }
+
+        struct TestInput;
+
+        impl TypeDef for TestInput {
+            const INFO: TypeInfo = <Test as TypeDef>::INFO_DE;
+        }
+
+        let mut buf = Vec::new();
+        let options = ZodSchemaOptions {
+            header: None,
+            ..Default::default()
+        };
+        write_zod_schemas::<_, TestInput>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import { z } from "zod";
+
+export const TestSchema = z.union([z.object({ "type": z.literal("A") }), z.object({ "type": z.literal("Unknown") }), z.object({ "type": z.string() })]);
+export type Test = z.infer<typeof TestSchema>;
+"#
+        );
+    }
+
+    #[test]
+    fn integer_reprs() {
+        #[derive(Serialize, TypeDef)]
+        struct Test(u64, u128);
+
+        let mut buf = Vec::new();
+        let options = ZodSchemaOptions {
+            header: None,
+            int64_repr: IntegerRepr::BigInt,
+            ..Default::default()
+        };
+        write_zod_schemas::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq_str!(
+            result,
+            r#"import { z } from "zod";
+
+export const U64Schema = z.bigint();
+export type U64 = z.infer<typeof U64Schema>;
+export const U128Schema = z.string();
+export type U128 = z.infer<typeof U128Schema>;
+export const TestSchema = z.tuple([U64Schema, U128Schema]);
+export type Test = z.infer<typeof TestSchema>;
+"#
+        );
+    }
+}
+
+mod json_schema {
+    #![allow(dead_code)]
+
+    use super::*;
+    use typescript_type_def::{write_json_schema, JsonSchemaOptions};
+
+    #[test]
+    fn json_schema() {
+        #[derive(Serialize, TypeDef)]
+        #[type_def(namespace = "x")]
+        struct Tree<T> {
+            value: T,
+            children: Vec<Tree<T>>,
+        }
+
+        /// A test.
+        #[derive(Serialize, TypeDef)]
+        #[serde(tag = "type")]
+        enum Test {
+            A {
+                /// A tree.
+                tree: Tree<(String, bool)>,
+            },
+            B {
+                #[serde(skip_serializing_if = "Option::is_none")]
+                map: Option<HashMap<String, std::num::NonZeroI8>>,
+            },
+        }
+
+        let mut buf = Vec::new();
+        let options = JsonSchemaOptions {
+            id: Some("https://example.com/test.json"),
+            ..Default::default()
+        };
+        let stats = write_json_schema::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        assert_eq!(stats.type_definitions, 3);
+        serde_json::from_str::<serde_json::Value>(&result).unwrap();
+        assert_eq_str!(
+            result,
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "https://example.com/test.json",
+  "$ref": "#/$defs/Test",
+  "$defs": {
+    "x.Tree<[string, boolean]>": {
+      "type": "object",
+      "properties": {
+        "value": {
+          "type": "array",
+          "prefixItems": [
+            {
+              "type": "string"
+            },
+            {
+              "type": "boolean"
+            }
+          ],
+          "items": false,
+          "minItems": 2
+        },
+        "children": {
+          "type": "array",
+          "items": {
+            "$ref": "#/$defs/x.Tree%3C%5Bstring,%20boolean%5D%3E"
+          }
+        }
+      },
+      "required": [
+        "value",
+        "children"
+      ]
+    },
+    "NonZeroI8": {
+      "type": "integer",
+      "minimum": -128,
+      "maximum": 127,
+      "not": {
+        "const": 0
+      }
+    },
+    "Test": {
+      "description": "A test.",
+      "oneOf": [
+        {
+          "allOf": [
+            {
+              "type": "object",
+              "properties": {
+                "type": {
+                  "const": "A"
+                }
+              },
+              "required": [
+                "type"
+              ]
+            },
+            {
+              "type": "object",
+              "properties": {
+                "tree": {
+                  "description": "A tree.",
+                  "$ref": "#/$defs/x.Tree%3C%5Bstring,%20boolean%5D%3E"
+                }
+              },
+              "required": [
+                "tree"
+              ]
+            }
+          ]
+        },
+        {
+          "allOf": [
+            {
+              "type": "object",
+              "properties": {
+                "type": {
+                  "const": "B"
+                }
+              },
+              "required": [
+                "type"
+              ]
+            },
+            {
+              "type": "object",
+              "properties": {
+                "map": {
+                  "type": "object",
+                  "additionalProperties": {
+                    "$ref": "#/$defs/NonZeroI8"
+                  }
+                }
+              }
+            }
+          ]
+        }
+      ]
+    }
+  }
+}
+"##
+        );
+    }
+
+    #[test]
+    fn integer_reprs() {
+        #[derive(Serialize, TypeDef)]
+        struct Test(u64, std::num::NonZeroU128, i128);
+
+        let mut buf = Vec::new();
+        let options = JsonSchemaOptions {
+            int64_repr: IntegerRepr::String,
+            ..Default::default()
+        };
+        write_json_schema::<_, Test>(&mut buf, options).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        serde_json::from_str::<serde_json::Value>(&result).unwrap();
+        assert_eq_str!(
+            result,
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$ref": "#/$defs/Test",
+  "$defs": {
+    "U64": {
+      "type": "string",
+      "pattern": "^[0-9]+$"
+    },
+    "NonZeroU128": {
+      "type": "string",
+      "pattern": "^0*[1-9][0-9]*$"
+    },
+    "I128": {
+      "type": "string",
+      "pattern": "^-?[0-9]+$"
+    },
+    "Test": {
+      "type": "array",
+      "prefixItems": [
+        {
+          "$ref": "#/$defs/U64"
+        },
+        {
+          "$ref": "#/$defs/NonZeroU128"
+        },
+        {
+          "$ref": "#/$defs/I128"
+        }
+      ],
+      "items": false,
+      "minItems": 3
+    }
+  }
+}
+"##
+        );
+    }
+
+    #[test]
+    fn other_variant() {
+        #[derive(Deserialize, TypeDef)]
+        #[serde(tag = "type")]
+        enum Test {
+            A,
+            #[serde(other)]
+            Unknown,
+        }
+
+        struct TestInput;
+
+        impl TypeDef for TestInput {
+            const INFO: TypeInfo = <Test as TypeDef>::INFO_DE;
+        }
+
+        let mut buf = Vec::new();
+        write_json_schema::<_, TestInput>(&mut buf, Default::default())
+            .unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        serde_json::from_str::<serde_json::Value>(&result).unwrap();
+        assert_eq_str!(
+            result,
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$ref": "#/$defs/Test",
+  "$defs": {
+    "Test": {
+      "anyOf": [
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "const": "A"
+            }
+          },
+          "required": [
+            "type"
+          ]
+        },
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "const": "Unknown"
+            }
+          },
+          "required": [
+            "type"
+          ]
+        },
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "description": "Any other tag.",
+              "type": "string"
+            }
+          },
+          "required": [
+            "type"
+          ]
+        }
+      ]
+    }
+  }
+}
+"##
+        );
+    }
+
+    #[test]
+    fn polymorphic_recursion() {
+        #[derive(Serialize, TypeDef)]
+        struct Nested<T> {
+            value: T,
+            inner: Option<Box<Nested<Vec<T>>>>,
+        }
+
+        let mut buf = Vec::new();
+        let err =
+            write_json_schema::<_, Nested<u8>>(&mut buf, Default::default())
+                .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq_str!(
+            err.to_string(),
+            "type `Nested<U8>` refers to itself as `Nested<(U8)[]>`, which \
+             can't be described without generics"
+        );
+    }
+
+    #[test]
+    fn constant_recursion() {
+        #[derive(Serialize, TypeDef)]
+        struct Foo<T> {
+            a: T,
+            b: Option<Box<Foo<u8>>>,
+        }
+
+        let mut buf = Vec::new();
+        write_json_schema::<_, Foo<String>>(&mut buf, Default::default())
+            .unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        serde_json::from_str::<serde_json::Value>(&result).unwrap();
+        assert_eq_str!(
+            result,
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$ref": "#/$defs/Foo%3Cstring%3E",
+  "$defs": {
+    "U8": {
+      "type": "integer",
+      "minimum": 0,
+      "maximum": 255
+    },
+    "Foo<U8>": {
+      "type": "object",
+      "properties": {
+        "a": {
+          "$ref": "#/$defs/U8"
+        },
+        "b": {
+          "anyOf": [
+            {
+              "$ref": "#/$defs/Foo%3CU8%3E"
+            },
+            {
+              "type": "null"
+            }
+          ]
+        }
+      },
+      "required": [
+        "a",
+        "b"
+      ]
+    },
+    "Foo<string>": {
+      "type": "object",
+      "properties": {
+        "a": {
+          "type": "string"
+        },
+        "b": {
+          "anyOf": [
+            {
+              "$ref": "#/$defs/Foo%3CU8%3E"
+            },
+            {
+              "type": "null"
+            }
+          ]
+        }
+      },
+      "required": [
+        "a",
+        "b"
+      ]
+    }
+  }
+}
+"##
+        );
+    }
+
+    #[test]
+    fn deny_unknown_fields() {
+        #[derive(Serialize, TypeDef)]
+        #[serde(deny_unknown_fields)]
+        struct Closed {
+            a: String,
+        }
+
+        #[derive(Serialize, TypeDef)]
+        #[serde(tag = "type", deny_unknown_fields)]
+        enum Test {
+            A { closed: Closed },
+            B,
+        }
+
+        let mut buf = Vec::new();
+        write_json_schema::<_, Test>(&mut buf, Default::default()).unwrap();
+        let result = String::from_utf8(buf).unwrap();
+
+        serde_json::from_str::<serde_json::Value>(&result).unwrap();
+        assert_eq_str!(
+            result,
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$ref": "#/$defs/Test",
+  "$defs": {
+    "Closed": {
+      "type": "object",
+      "properties": {
+        "a": {
+          "type": "string"
+        }
+      },
+      "required": [
+        "a"
+      ],
+      "additionalProperties": false
+    },
+    "Test": {
+      "oneOf": [
+        {
+          "allOf": [
+            {
+              "type": "object",
+              "properties": {
+                "type": {
+                  "const": "A"
+                }
+              },
+              "required": [
+                "type"
+              ]
+            },
+            {
+              "type": "object",
+              "properties": {
+                "closed": {
+                  "$ref": "#/$defs/Closed"
+                }
+              },
+              "required": [
+                "closed"
+              ]
+            }
+          ],
+          "unevaluatedProperties": false
+        },
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "const": "B"
+            }
+          },
+          "required": [
+            "type"
+          ]
+        }
+      ]
+    }
+  }
+}
+"##
+        );
     }
 }